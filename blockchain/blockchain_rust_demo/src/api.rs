@@ -0,0 +1,374 @@
+// HTTP üzerinden JSON API
+// Menüye erişemeyen betikler ve test araçları zinciri bu uç noktalarla yönetir:
+//   GET  /chain              Zincirin özeti ve tüm bloklar
+//   GET  /blocks/<index|hash> Tek bir blok (sayıysa indeks, değilse hash olarak aranır)
+//   GET  /mempool            Bekleyen işlemler
+//   POST /transactions       İmzalı işlemi (JSON) havuza ekler
+//   GET  /mining             Madencilik durumu
+//   POST /mining/start       Arka planda sürekli madenciliği başlatır (gövde: {"address": "..."} isteğe bağlı)
+//   POST /mining/stop        Madenciliği durdurur
+//   GET  /validate           Zinciri doğrular ve bulunan ihlalleri listeler
+
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Read};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::consensus::{Consensus, SealError};
+use crate::merkle::Hash;
+use crate::miner::CancelToken;
+use crate::node::Node;
+use crate::storage::ChainStore;
+use crate::transaction::Transaction;
+use crate::{publish_block, Blockchain};
+
+// İstek gövdesinin en büyük boyutu (byte); daha uzun gövdeler okunmadan reddedilir
+const MAX_BODY: u64 = 64 * 1024;
+
+// Bir isteğin karşılanamama nedeni; her biri bir HTTP durum koduna karşılık gelir
+#[derive(Debug)]
+pub enum ApiError {
+    NotFound,           // Yol ya da blok bulunamadı
+    BadRequest(String), // Gövde çözülemedi ya da işlem geçersiz
+    Conflict(String),   // İstek mevcut durumla çelişiyor (ör. madencilik zaten sürüyor)
+    PayloadTooLarge,    // Gövde MAX_BODY byte'ı aşıyor
+}
+
+impl ApiError {
+    fn status(&self) -> u16 {
+        match self {
+            ApiError::NotFound => 404,
+            ApiError::BadRequest(_) => 400,
+            ApiError::Conflict(_) => 409,
+            ApiError::PayloadTooLarge => 413,
+        }
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::NotFound => write!(f, "not found"),
+            ApiError::BadRequest(reason) => write!(f, "bad request: {}", reason),
+            ApiError::Conflict(reason) => write!(f, "conflict: {}", reason),
+            ApiError::PayloadTooLarge => write!(f, "request body exceeds {} bytes", MAX_BODY),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+// API'nin eriştiği paylaşılan durum (klonlar aynı zinciri ve madencilik durumunu gösterir)
+pub struct Api<C: Consensus> {
+    chain: Arc<Mutex<Blockchain<C>>>,
+    store: ChainStore,
+    node: Option<Node<C>>,                    // Düğüm modunda kazılan bloklar ve işlemler eşlere de gönderilir
+    miner_address: String,                    // Adres verilmezse ödülün gönderileceği adres
+    mining: Arc<Mutex<Option<CancelToken>>>, // Sürmekte olan madenciliğin iptal jetonu
+}
+
+// Çalışan sunucu
+pub struct ApiServer {
+    address: SocketAddr,
+    handle: JoinHandle<()>,
+}
+
+impl ApiServer {
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    // Sunucu iş parçacığı bitene kadar (yani program kapanana kadar) bekler
+    pub fn join(self) {
+        let _ = self.handle.join();
+    }
+}
+
+// derive, motorun da Clone olmasını şart koşacağı için elle yazılır
+impl<C: Consensus> Clone for Api<C> {
+    fn clone(&self) -> Self {
+        Api {
+            chain: Arc::clone(&self.chain),
+            store: self.store.clone(),
+            node: self.node.clone(),
+            miner_address: self.miner_address.clone(),
+            mining: Arc::clone(&self.mining),
+        }
+    }
+}
+
+impl<C: Consensus> Api<C> {
+    pub fn new(
+        chain: Arc<Mutex<Blockchain<C>>>,
+        store: ChainStore,
+        node: Option<Node<C>>,
+        miner_address: String,
+    ) -> Api<C> {
+        Api { chain, store, node, miner_address, mining: Arc::default() }
+    }
+
+    // Yerel makinede (127.0.0.1) verilen portu dinler; istekler sırayla tek bir iş parçacığında karşılanır
+    // Kimlik doğrulama olmadığı için API dış ağlara açılmaz
+    // (Port 0 verilirse işletim sistemi boş bir port seçer)
+    pub fn start(self, port: u16) -> io::Result<ApiServer> {
+        let server = Server::http(("127.0.0.1", port)).map_err(io::Error::other)?;
+        let address = server
+            .server_addr()
+            .to_ip()
+            .ok_or_else(|| io::Error::other("API server is not listening on an IP address"))?;
+        let handle = thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let response = self.handle(&mut request);
+                let _ = request.respond(response); // İstemci bağlantıyı kapatmışsa önemsenmez
+            }
+        });
+        Ok(ApiServer { address, handle })
+    }
+
+    fn handle(&self, request: &mut Request) -> Response<io::Cursor<Vec<u8>>> {
+        let mut body = String::new();
+        let result = match request.as_reader().take(MAX_BODY + 1).read_to_string(&mut body) {
+            Ok(read) if read as u64 > MAX_BODY => Err(ApiError::PayloadTooLarge),
+            Ok(_) => self.route(request.method(), request.url(), &body),
+            Err(err) => Err(ApiError::BadRequest(err.to_string())),
+        };
+        let (status, value) = match result {
+            Ok(value) => (200, value),
+            Err(err) => (err.status(), json!({ "error": err.to_string() })),
+        };
+        let content_type = Header::from_bytes("Content-Type", "application/json").expect("valid header");
+        Response::from_string(value.to_string()).with_status_code(status).with_header(content_type)
+    }
+
+    fn route(&self, method: &Method, url: &str, body: &str) -> Result<Value, ApiError> {
+        let path = url.split('?').next().unwrap_or_default().trim_end_matches('/');
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+        match (method, &segments[..]) {
+            (Method::Get, ["chain"]) => Ok(self.chain_summary()),
+            (Method::Get, ["blocks", id]) => self.block(id),
+            (Method::Get, ["mempool"]) => Ok(self.mempool()),
+            (Method::Post, ["transactions"]) => self.submit_transaction(body),
+            (Method::Get, ["mining"]) => Ok(json!({ "mining": self.mining.lock().unwrap().is_some() })),
+            (Method::Post, ["mining", "start"]) => self.start_mining(body),
+            (Method::Post, ["mining", "stop"]) => self.stop_mining(),
+            (Method::Get, ["validate"]) => Ok(self.validate()),
+            _ => Err(ApiError::NotFound),
+        }
+    }
+
+    fn chain_summary(&self) -> Value {
+        let chain = self.chain.lock().unwrap();
+        json!({
+            "height": chain.blocks.len(),
+            "bits": chain.bits,
+            "total_work": chain.total_work().to_string(), // JSON sayıları 128 bit tamsayıyı taşıyamaz
+            "blocks": chain.blocks,
+        })
+    }
+
+    fn block(&self, id: &str) -> Result<Value, ApiError> {
+        let chain = self.chain.lock().unwrap();
+        let block = match id.parse::<usize>() {
+            Ok(height) => chain.block_at(height),
+            Err(_) => crate::from_hex(id).and_then(|hash| Hash::try_from(hash).ok()).and_then(|hash| chain.block_by_hash(&hash)),
+        };
+        block.map(|block| json!(block)).ok_or(ApiError::NotFound)
+    }
+
+    // Geçerlilik ve varsa her ihlalin blok sırası ile açıklaması
+    fn validate(&self) -> Value {
+        let report = self.chain.lock().unwrap().validate();
+        let violations: Vec<Value> = report
+            .violations
+            .iter()
+            .map(|violation| json!({ "index": violation.index, "reason": violation.kind.to_string() }))
+            .collect();
+        json!({ "valid": report.is_valid(), "violations": violations })
+    }
+
+    fn mempool(&self) -> Value {
+        let chain = self.chain.lock().unwrap();
+        let entries: Vec<Value> = chain
+            .mempool
+            .entries()
+            .iter()
+            .map(|entry| {
+                json!({
+                    "txid": entry.transaction.txid(),
+                    "fee": entry.fee,
+                    "size": entry.size,
+                    "received": entry.received,
+                })
+            })
+            .collect();
+        json!({ "size": chain.mempool.total_size(), "transactions": entries })
+    }
+
+    fn submit_transaction(&self, body: &str) -> Result<Value, ApiError> {
+        let transaction: Transaction =
+            serde_json::from_str(body).map_err(|err| ApiError::BadRequest(err.to_string()))?;
+        let fee = self
+            .chain
+            .lock()
+            .unwrap()
+            .submit_transaction(transaction.clone())
+            .map_err(|err| ApiError::BadRequest(err.to_string()))?;
+        if let Some(node) = &self.node {
+            node.broadcast_transaction(&transaction);
+        }
+        Ok(json!({ "txid": transaction.txid(), "fee": fee }))
+    }
+
+    // Durdurulana kadar havuzdan blok hazırlayıp kazan bir iş parçacığı başlatır
+    fn start_mining(&self, body: &str) -> Result<Value, ApiError> {
+        let address = match body.trim() {
+            "" => self.miner_address.clone(),
+            body => {
+                let request: Value = serde_json::from_str(body).map_err(|err| ApiError::BadRequest(err.to_string()))?;
+                match request.get("address") {
+                    Some(Value::String(address)) => address.clone(),
+                    None => self.miner_address.clone(),
+                    Some(_) => return Err(ApiError::BadRequest(String::from("address must be a string"))),
+                }
+            }
+        };
+
+        let mut mining = self.mining.lock().unwrap();
+        if mining.is_some() {
+            return Err(ApiError::Conflict(String::from("mining is already running")));
+        }
+        let cancel = CancelToken::default();
+        *mining = Some(cancel.clone());
+        let api = self.clone();
+        let miner_address = address.clone();
+        thread::spawn(move || api.mine_until_cancelled(&miner_address, &cancel));
+        Ok(json!({ "mining": true, "address": address }))
+    }
+
+    fn stop_mining(&self) -> Result<Value, ApiError> {
+        match self.mining.lock().unwrap().take() {
+            Some(cancel) => {
+                cancel.cancel();
+                Ok(json!({ "mining": false }))
+            }
+            None => Err(ApiError::Conflict(String::from("mining is not running"))),
+        }
+    }
+
+    fn mine_until_cancelled(&self, address: &str, cancel: &CancelToken) {
+        while !cancel.is_cancelled() {
+            // Madencilik sırasında zincir kilitli tutulmaz; ağdan ve API'den gelen istekler işlenmeye devam eder
+            let (mut block, miner, consensus) = {
+                let mut chain = self.chain.lock().unwrap();
+                match chain.block_template(address) {
+                    Ok(block) => (block, chain.miner.clone(), Arc::clone(&chain.consensus)),
+                    Err(err) => {
+                        eprintln!("[api] failed to assemble block: {}", err);
+                        break;
+                    }
+                }
+            };
+            let stats = match consensus.seal(&mut block, &miner, cancel, &mut |_| {}) {
+                Ok(stats) => stats,
+                Err(SealError::Cancelled) => break,
+                Err(err) => {
+                    eprintln!("[api] failed to seal block: {}", err);
+                    break;
+                }
+            };
+            match publish_block(&self.chain, &self.store, self.node.as_ref(), block, Some(stats)) {
+                Ok(events) => events.iter().for_each(|event| println!("[api] {}", event)),
+                Err(err) => eprintln!("[api] mined block rejected: {}", err),
+            }
+        }
+        // Hata yüzünden durulduysa madencilik durumu da temizlenir (durdurma isteği onu zaten temizlemiştir)
+        if !cancel.is_cancelled() {
+            *self.mining.lock().unwrap() = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{chain_with, funded_chain, temp_store};
+    use crate::transaction::TxOutput;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::time::{Duration, Instant};
+
+    fn start(blockchain: Blockchain, name: &str) -> (ApiServer, Arc<Mutex<Blockchain>>, ChainStore) {
+        let store = temp_store(&format!("api-{}", name));
+        let chain = Arc::new(Mutex::new(blockchain));
+        let api = Api::new(Arc::clone(&chain), store.clone(), None, String::from("api-miner"));
+        (api.start(0).unwrap(), chain, store)
+    }
+
+    // Basit bir HTTP/1.1 isteği gönderir; durum kodunu ve JSON gövdeyi döndürür
+    fn request(server: &ApiServer, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(("127.0.0.1", server.address().port())).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn serves_chain_and_blocks_by_index_or_hash() {
+        let blockchain = chain_with(2, "miner");
+        let hash = crate::to_hex(&blockchain.blocks[1].hash);
+        let (server, _, _) = start(blockchain, "read");
+
+        let (status, chain) = request(&server, "GET", "/chain", "");
+        assert_eq!(status, 200);
+        assert_eq!(chain["height"], 2);
+        assert_eq!(chain["blocks"][1]["hash"], hash);
+
+        assert_eq!(request(&server, "GET", "/blocks/1", "").1["hash"], hash);
+        assert_eq!(request(&server, "GET", &format!("/blocks/{}", hash), "").1["index"], 1);
+        assert_eq!(request(&server, "GET", "/blocks/7", "").0, 404);
+        assert_eq!(request(&server, "GET", "/validate", "").1["valid"], true);
+        assert_eq!(request(&server, "DELETE", "/chain", "").0, 404);
+    }
+
+    #[test]
+    fn submitted_transaction_is_mined_in_background() {
+        let (blockchain, key, _) = funded_chain(1);
+        let payment = vec![TxOutput { amount: 7, address: String::from("bob"), script: None }];
+        let transaction = blockchain.utxos.build_transaction(&key, payment, 2).unwrap();
+        let (server, chain, store) = start(blockchain, "mining");
+
+        let body = serde_json::to_string(&transaction).unwrap();
+        let (status, submitted) = request(&server, "POST", "/transactions", &body);
+        assert_eq!((status, submitted["fee"].as_u64()), (200, Some(2)));
+        assert_eq!(request(&server, "POST", "/transactions", &body).0, 400);
+        assert_eq!(request(&server, "POST", "/transactions", "not json").0, 400);
+        assert_eq!(request(&server, "POST", "/transactions", &" ".repeat(MAX_BODY as usize + 1)).0, 413);
+
+        assert_eq!(request(&server, "POST", "/mining/start", "").0, 200);
+        assert_eq!(request(&server, "POST", "/mining/start", "").0, 409);
+        let started = Instant::now();
+        while chain.lock().unwrap().balance_of("bob") == 0 {
+            assert!(started.elapsed() < Duration::from_secs(10), "transaction was not mined");
+            thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(request(&server, "POST", "/mining/stop", "").0, 200);
+        assert_eq!(request(&server, "GET", "/mining", "").1["mining"], false);
+        assert_eq!(request(&server, "POST", "/mining/stop", "").0, 409);
+        let _ = std::fs::remove_file(store.path());
+    }
+}
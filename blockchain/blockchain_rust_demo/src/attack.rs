@@ -0,0 +1,248 @@
+// Atölye senaryoları: doğrulamanın ve iş kanıtının neden önemli olduğunu gösteren saldırılar
+// Senaryolar zincirin bir kopyası üzerinde çalışır; diskteki zincir ve ağdaki eşler etkilenmez
+//
+// 1. Geçmişteki bir bloğun verisi değiştirilir: Merkle kökü ve sonraki bağlantılar bozulur
+// 2. Değiştirilen bloktan itibaren zincir yeniden kazılır: hash'ler düzelir ama dürüst düğümler
+//    eşit işteki bu dalı benimsemez (imzalı işlemler değiştirildiyse defter de hâlâ geçersizdir)
+// 3. Hash gücünün çoğuna sahip bir saldırgan gizli bir dal kazar, dürüst zinciri geçince yayınlar
+//    ve en çok iş kuralı gereği dürüst düğümler zinciri yeniden düzenler
+
+use std::fmt::{self, Display, Formatter};
+
+use rand::Rng;
+
+use crate::consensus::{Consensus, SealError};
+use crate::fork::ChainEvent;
+use crate::header::ZERO_HASH;
+use crate::miner::CancelToken;
+use crate::utxo::LedgerError;
+use crate::{Block, BlockError, Blockchain};
+
+// Saldırgan ve dürüst madencilerin ödül adresleri (gösterim amaçlı)
+pub const ATTACKER_ADDRESS: &str = "attacker";
+pub const HONEST_ADDRESS: &str = "honest";
+
+// Bir senaryo tamamlanamadığında dönen hatalar
+#[derive(Debug)]
+pub enum ScenarioError {
+    NoSuchBlock,         // Verilen sırada blok yok
+    NoSuchOutput,        // Blokta verilen işlem ya da çıktı yok
+    Seal(SealError),     // Blok mühürlenemedi (madencilik iptal edildi ya da sıra bu düğümde değil)
+    Block(BlockError),   // Kazılan blok zincire eklenemedi
+    Ledger(LedgerError), // Aday blok hazırlanamadı
+}
+
+impl Display for ScenarioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioError::NoSuchBlock => write!(f, "no block at that index"),
+            ScenarioError::NoSuchOutput => write!(f, "no such transaction output in the block"),
+            ScenarioError::Seal(err) => write!(f, "{}", err),
+            ScenarioError::Block(err) => write!(f, "{}", err),
+            ScenarioError::Ledger(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+impl From<BlockError> for ScenarioError {
+    fn from(err: BlockError) -> Self {
+        ScenarioError::Block(err)
+    }
+}
+
+impl From<SealError> for ScenarioError {
+    fn from(err: SealError) -> Self {
+        ScenarioError::Seal(err)
+    }
+}
+
+impl From<LedgerError> for ScenarioError {
+    fn from(err: LedgerError) -> Self {
+        ScenarioError::Ledger(err)
+    }
+}
+
+// Yeniden kazımanın maliyeti
+pub struct RemineStats {
+    pub blocks: usize,  // Yeniden kazılan blok sayısı
+    pub attempts: u64,  // Toplam hash denemesi
+}
+
+// 51% saldırısının sonucu
+pub struct AttackReport {
+    pub fork_height: usize,       // Saldırganın dalının ayrıldığı yükseklik
+    pub honest_blocks: usize,     // Bu sürede dürüst madencilerin kazdığı blok sayısı
+    pub attacker_blocks: usize,   // Saldırganın gizli dalında kazdığı blok sayısı
+    pub published: bool,          // Saldırgan dalını yayınladı mı?
+    pub events: Vec<ChainEvent>,  // Yayınlanan dalın dürüst zincirde yol açtığı değişiklikler
+}
+
+impl AttackReport {
+    // Dürüst düğümler saldırganın dalını benimsedi mi?
+    pub fn succeeded(&self) -> bool {
+        self.events.iter().any(|event| matches!(event, ChainEvent::Reorganized { .. }))
+    }
+}
+
+impl<C: Consensus> Blockchain<C> {
+    // Geçmişteki bir işlemin çıktısını değiştirir; başlık, nonce ve hash'e dokunulmaz
+    // (Gerçek bir saldırgan diskteki ya da aktarılan veriyi bu şekilde değiştirebilir)
+    pub fn tamper_output(
+        &mut self,
+        block_index: usize,
+        tx_index: usize,
+        output_index: usize,
+        amount: Option<u64>,
+        address: Option<String>,
+    ) -> Result<(), ScenarioError> {
+        let block = self.blocks.get_mut(block_index).ok_or(ScenarioError::NoSuchBlock)?;
+        let output = block
+            .transactions
+            .get_mut(tx_index)
+            .and_then(|transaction| transaction.outputs.get_mut(output_index))
+            .ok_or(ScenarioError::NoSuchOutput)?;
+        if let Some(amount) = amount {
+            output.amount = amount;
+        }
+        if let Some(address) = address {
+            output.address = address;
+        }
+        self.reindex();
+        Ok(())
+    }
+
+    // from sırasındaki bloktan uca kadar Merkle kökünü ve önceki hash'i yeniden hesaplayıp blokları tekrar mühürler
+    // Hash'ler ve bağlantılar geçerli hale gelir; defter kuralları (imzalar, ödül) ise kazımayla düzelmez
+    pub fn remine_from(&mut self, from: usize, cancel: &CancelToken) -> Result<RemineStats, ScenarioError> {
+        if from >= self.blocks.len() {
+            return Err(ScenarioError::NoSuchBlock);
+        }
+        let mut attempts = 0;
+        for i in from..self.blocks.len() {
+            let previous_block_hash = if i == 0 { ZERO_HASH } else { self.blocks[i - 1].hash };
+            let mut block = self.blocks[i].clone();
+            block.previous_block_hash = previous_block_hash;
+            block.merkle_root = block.calculate_merkle_root();
+            block.nonce = 0;
+            attempts += self.seal(&mut block, cancel)?.attempts;
+            self.blocks[i] = block;
+        }
+        // Ucun defteri yeni verilere göre kurulur; defter geçersizse eski hali kalır (doğrulama raporu hatayı gösterir)
+        if let Ok(utxos) = self.replay_ledger() {
+            self.utxos = utxos;
+        }
+        self.reindex();
+        Ok(RemineStats { blocks: self.blocks.len() - from, attempts })
+    }
+}
+
+// Blokları bir düğüme ağdan gelmiş gibi sırayla verir ve zincir seçiminin sonucunu döndürür
+// Düğümün zaten bildiği bloklar atlanır
+pub fn present_blocks<C: Consensus>(
+    chain: &mut Blockchain<C>,
+    blocks: &[Block],
+) -> Result<Vec<ChainEvent>, BlockError> {
+    let mut events = Vec::new();
+    for block in blocks {
+        if !chain.contains(&block.hash) {
+            events.extend(chain.receive_block(block.clone())?);
+        }
+    }
+    Ok(events)
+}
+
+// Hash gücünün attacker_share kadarına sahip saldırganın dürüst zincirin ucundan gizli bir dal kazdığı yarış
+// Her turda sıradaki bloğu saldırganın bulma olasılığı attacker_share'dir
+// Dürüst zincir en az confirmations blok ilerledikten sonra saldırganın dalı daha çok işe sahipse dal yayınlanır;
+// max_blocks blok kazıldığı halde öne geçilemezse saldırgan vazgeçer
+pub fn majority_attack<C: Consensus>(
+    honest: &mut Blockchain<C>,
+    attacker_share: f64,
+    confirmations: usize,
+    max_blocks: usize,
+    rng: &mut impl Rng,
+    cancel: &CancelToken,
+) -> Result<AttackReport, ScenarioError> {
+    let fork_height = honest.blocks.len();
+    let mut attacker = honest.clone();
+    let mut report =
+        AttackReport { fork_height, honest_blocks: 0, attacker_blocks: 0, published: false, events: Vec::new() };
+
+    while report.honest_blocks + report.attacker_blocks < max_blocks {
+        if rng.gen_bool(attacker_share.clamp(0.0, 1.0)) {
+            mine_next(&mut attacker, ATTACKER_ADDRESS, cancel)?;
+            report.attacker_blocks += 1;
+        } else {
+            mine_next(honest, HONEST_ADDRESS, cancel)?;
+            report.honest_blocks += 1;
+        }
+        if report.honest_blocks >= confirmations && attacker.total_work() > honest.total_work() {
+            report.published = true;
+            report.events = present_blocks(honest, &attacker.blocks[fork_height..])?;
+            break;
+        }
+    }
+    Ok(report)
+}
+
+// Zincirin ucuna ödülü verilen adrese giden boş bir blok kazar
+fn mine_next<C: Consensus>(chain: &mut Blockchain<C>, miner: &str, cancel: &CancelToken) -> Result<(), ScenarioError> {
+    let mut block = chain.candidate_block(miner, Vec::new())?;
+    chain.seal(&mut block, cancel)?;
+    chain.add_block(block)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::chain_with;
+    use crate::utxo::BLOCK_REWARD;
+    use crate::validation::{Violation, ViolationKind};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn tampering_is_detected_and_remining_hides_it_from_validation_only() {
+        let honest = chain_with(4, "miner");
+        let mut rewritten = honest.clone();
+        rewritten.tamper_output(1, 0, 0, None, Some(String::from("thief"))).unwrap();
+        let report = rewritten.validate();
+        let merkle_violation = |v: &Violation| v.index == 1 && matches!(v.kind, ViolationKind::MerkleRootMismatch);
+        assert!(report.violations.iter().any(merkle_violation));
+
+        let stats = rewritten.remine_from(1, &CancelToken::default()).unwrap();
+        assert_eq!(stats.blocks, 3);
+        assert!(rewritten.validate().is_valid());
+        assert_eq!(rewritten.balance_of("thief"), BLOCK_REWARD);
+
+        // Eşit işteki yeniden yazılmış dal dürüst düğümün zincirini değiştirmez
+        let mut node = honest.clone();
+        let events = present_blocks(&mut node, &rewritten.blocks).unwrap();
+        assert!(events.iter().all(|event| matches!(event, ChainEvent::SideBranch { .. })));
+        assert_eq!(node.blocks.last().unwrap().hash, honest.blocks.last().unwrap().hash);
+    }
+
+    #[test]
+    fn majority_attacker_reorganizes_the_honest_chain() {
+        let mut honest = chain_with(2, "miner");
+        let mut rng = StdRng::seed_from_u64(7);
+        let report = majority_attack(&mut honest, 0.9, 1, 30, &mut rng, &CancelToken::default()).unwrap();
+        assert!(report.published && report.succeeded());
+        assert!(report.attacker_blocks > report.honest_blocks);
+        assert_eq!(honest.blocks.len(), 2 + report.attacker_blocks);
+        assert_eq!(honest.balance_of(HONEST_ADDRESS), 0);
+    }
+
+    #[test]
+    fn minority_attacker_gives_up() {
+        let mut honest = chain_with(2, "miner");
+        let mut rng = StdRng::seed_from_u64(7);
+        let report = majority_attack(&mut honest, 0.0, 1, 5, &mut rng, &CancelToken::default()).unwrap();
+        assert!(!report.published && !report.succeeded());
+        assert_eq!(report.honest_blocks, 5);
+        assert_eq!(honest.balance_of(ATTACKER_ADDRESS), 0);
+    }
+}
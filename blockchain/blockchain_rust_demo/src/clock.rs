@@ -0,0 +1,172 @@
+// Zincirin zaman kaynağı
+// Blok zaman damgaları ve havuzun bekleme süreleri doğrudan sistem saatinden değil bir Clock üzerinden okunur
+// Gerçek çalışmada sistem saati kullanılır; tohumlu (seeded) çalışmada ve testlerde ise zincire eklenen her blokla
+// sabit adımla ilerleyen sanal bir saat kullanılır. Böylece aynı tohum ve aynı girdi dizisi byte byte aynı zinciri üretir
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Tohumlu çalışmada sanal saatin başladığı an (Bitcoin genesis bloğunun zaman damgası)
+pub const SIMULATED_EPOCH: u64 = 1_231_006_505;
+
+// Unix saniyesi veren zaman kaynağı
+pub trait Clock: Send + Sync {
+    // Saati değiştirmeden şu anki zamanı okur
+    fn now(&self) -> u64;
+
+    // Zincire bir blok eklendiğinde çağrılır (sistem saati kendiliğinden ilerler)
+    fn advance(&self) {}
+}
+
+// İşletim sisteminin saati
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards").as_secs()
+    }
+}
+
+// Zincire eklenen her blokla step saniye ilerleyen sanal saat
+// Okumalar saati ilerletmez; n. bloğun zaman damgası başlangıç + n * step olur
+pub struct SimulatedClock {
+    now: AtomicU64, // Şu anki zaman
+    step: u64,      // İki blok arasındaki fark (saniye)
+}
+
+impl SimulatedClock {
+    pub fn new(start: u64, step: u64) -> SimulatedClock {
+        SimulatedClock { now: AtomicU64::new(start), step }
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> u64 {
+        self.now.load(Ordering::Relaxed)
+    }
+
+    fn advance(&self) {
+        self.now.fetch_add(self.step, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::{EASIEST_BITS, TARGET_BLOCK_TIME};
+    use crate::miner::CancelToken;
+    use crate::tests::mine;
+    use crate::transaction::TxOutput;
+    use crate::utxo::LedgerError;
+    use crate::validation::ViolationKind;
+    use crate::wallet::address_of;
+    use crate::{to_hex, Blockchain};
+    use ed25519_dalek::SigningKey;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Tohumdan türetilen anahtarla, sanal saatle ve tek iş parçacıklı madenciyle kurulan zincir:
+    // üç blok kazılır, ikincisinde bob'a ödeme yapılır
+    fn seeded_chain(seed: u64) -> Blockchain {
+        let key = SigningKey::generate(&mut StdRng::seed_from_u64(seed));
+        let miner = address_of(&key.verifying_key());
+        let mut blockchain = Blockchain::with_difficulty(EASIEST_BITS);
+        blockchain.miner.threads = 1;
+        for amount in [0, 5, 0] {
+            if amount > 0 {
+                let payment = vec![TxOutput { amount, address: String::from("bob"), script: None }];
+                blockchain.send(&key, payment, 1).unwrap();
+            }
+            let mut block = blockchain.block_template(&miner).unwrap();
+            blockchain.seal(&mut block, &CancelToken::default()).unwrap();
+            blockchain.add_block(block).unwrap();
+        }
+        blockchain
+    }
+
+    fn serialized(blockchain: &Blockchain) -> Vec<u8> {
+        serde_json::to_vec(&blockchain.blocks).unwrap()
+    }
+
+    #[test]
+    fn simulated_clock_advances_by_its_step() {
+        let clock = SimulatedClock::new(100, 10);
+        assert_eq!([clock.now(), clock.now()], [100, 100]); // Okumak saati ilerletmez
+        clock.advance();
+        clock.advance();
+        assert_eq!(clock.now(), 120);
+    }
+
+    #[test]
+    fn same_seed_produces_identical_chains() {
+        let first = seeded_chain(42);
+        let second = seeded_chain(42);
+        assert_eq!(serialized(&first), serialized(&second));
+        assert_ne!(serialized(&first), serialized(&seeded_chain(43)));
+
+        // Bloklar birbirine bağlı, her blok sanal saati tam bir adım ilerletmiş ve zincir geçerli
+        // (havuza işlem göndermek ya da şablon hazırlamak saati ilerletmez)
+        assert!(first.blocks.windows(2).all(|pair| pair[1].previous_block_hash == pair[0].hash));
+        let timestamps: Vec<u64> = first.blocks.iter().map(|block| block.timestamp).collect();
+        assert_eq!(timestamps, [0, 1, 2].map(|height| SIMULATED_EPOCH + height * TARGET_BLOCK_TIME));
+        assert!(first.validate().is_valid());
+        assert_eq!(first.balance_of("bob"), 5);
+    }
+
+    #[test]
+    fn seeded_genesis_hash_is_stable() {
+        // Başlık kodlaması, hash fonksiyonu, coinbase ya da madencilik değişirse bu değer de değişir
+        let genesis = &seeded_chain(42).blocks[0];
+        assert_eq!(genesis.timestamp, SIMULATED_EPOCH);
+        assert_eq!(to_hex(&genesis.hash), "2f684478561433474696b36201132bfbab37889921163a66a48cefa0daafd294");
+    }
+
+    #[test]
+    fn tampering_a_seeded_chain_is_reported_at_the_block() {
+        let mut blockchain = seeded_chain(42);
+        blockchain.blocks[1].transactions[1].outputs[0].amount = 500;
+        let report = blockchain.validate();
+        assert!(!report.is_valid());
+        assert_eq!(report.violations[0].index, 1);
+        assert!(matches!(report.violations[0].kind, ViolationKind::MerkleRootMismatch));
+    }
+
+    #[test]
+    fn broken_link_in_a_seeded_chain_is_reported_at_the_block() {
+        let mut blockchain = seeded_chain(42);
+        blockchain.blocks[2].previous_block_hash = blockchain.blocks[0].hash;
+        mine(&mut blockchain.blocks[2]);
+        let report = blockchain.validate();
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].index, 2);
+        assert!(matches!(report.violations[0].kind, ViolationKind::BrokenLink { .. }));
+    }
+
+    #[test]
+    fn forged_signature_in_a_seeded_chain_is_reported_at_the_block() {
+        // Merkle kökü güncellenip blok yeniden kazılsa bile değiştirilen işlemin imzası tutmaz
+        let mut blockchain = seeded_chain(42);
+        let block = &mut blockchain.blocks[1];
+        block.transactions[1].outputs[0].amount = 500;
+        block.merkle_root = block.calculate_merkle_root();
+        mine(block);
+        let report = blockchain.validate();
+        assert_eq!(report.violations[0].index, 1);
+        assert!(matches!(report.violations[0].kind, ViolationKind::Ledger(LedgerError::InvalidSignature { .. })));
+    }
+
+    #[test]
+    fn double_spend_in_a_seeded_chain_is_reported_at_the_block() {
+        // Bob'a yapılan ödeme son bloğa bir kez daha konur
+        let mut blockchain = seeded_chain(42);
+        let payment = blockchain.blocks[1].transactions[1].clone();
+        let block = &mut blockchain.blocks[2];
+        block.transactions.push(payment);
+        block.merkle_root = block.calculate_merkle_root();
+        mine(block);
+        let report = blockchain.validate();
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].index, 2);
+        assert!(matches!(report.violations[0].kind, ViolationKind::Ledger(LedgerError::MissingOutput { .. })));
+    }
+}
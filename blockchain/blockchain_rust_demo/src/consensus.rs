@@ -0,0 +1,272 @@
+// Uzlaşma (consensus) motorları: blokların kim tarafından ve nasıl mühürleneceğini (seal) belirler
+// Zincir kuralları (bağlantılar, Merkle kökü, defter, zaman damgası) motordan bağımsızdır; motor sadece
+//   - bir yükseklikteki bloğun taşıması gereken zorluğu,
+//   - bloğun nasıl mühürleneceğini (iş kanıtında nonce araması, yetki kanıtında sıradaki yetkilinin imzası),
+//   - mührün nasıl doğrulanacağını,
+//   - en çok iş kuralında bir bloğun ne kadar ağırlık taşıdığını
+// belirler. Böylece aynı araçlarla (menü, düğüm, API, senaryolar) farklı uzlaşma modelleri karşılaştırılabilir
+
+use std::fmt::{self, Display, Formatter};
+use std::time::{Duration, Instant};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::difficulty::{bits_to_target, block_work, hash_meets_target, retarget, RETARGET_INTERVAL, TARGET_BLOCK_TIME};
+use crate::miner::{CancelToken, Miner, MiningProgress, MiningStats};
+use crate::wallet::address_of;
+use crate::{from_hex, to_hex, Block};
+
+// Blok mühürlenemediğinde ya da mührü geçersiz olduğunda dönen hatalar
+#[derive(Debug)]
+pub enum SealError {
+    Cancelled,                                       // Mühürleme kullanıcı tarafından iptal edildi
+    InsufficientWork { bits: u32 },                  // Hash bloğun hedefini sağlamıyor
+    MissingSignature,                                // Yetki kanıtı bloğu imzasız
+    WrongSigner { expected: String, found: String }, // Blok sırası gelen yetkili tarafından imzalanmamış
+    BadSignature,                                    // İmza blok hash'ine ait değil
+    NotInTurn { signer: String },                    // Sıradaki yetkilinin anahtarı bu düğümde yok
+}
+
+impl Display for SealError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SealError::Cancelled => write!(f, "sealing cancelled"),
+            SealError::InsufficientWork { bits } => write!(f, "hash does not meet target {:#010x}", bits),
+            SealError::MissingSignature => write!(f, "block is not signed by an authority"),
+            SealError::WrongSigner { expected, found } => {
+                write!(f, "block is signed by {} but it is {}'s turn", found, expected)
+            }
+            SealError::BadSignature => write!(f, "block signature is invalid"),
+            SealError::NotInTurn { signer } => write!(f, "it is {}'s turn to seal and its key is not loaded", signer),
+        }
+    }
+}
+
+impl std::error::Error for SealError {}
+
+// Bir uzlaşma modelinin zincire kattığı kurallar
+pub trait Consensus: Send + Sync + 'static {
+    // Menüde ve API'de gösterilen ad
+    fn name(&self) -> &'static str;
+
+    // Verilen ataların ardından gelecek bloğun taşıması gereken zorluk (bits)
+    // Atalar genesis bloktan başlar; ana zincir için zincirin kendisi, yan dal için dalın ana zincirle birlikte yoludur
+    fn expected_bits(&self, genesis_bits: u32, ancestors: &[Block]) -> u32;
+
+    // Aday bloğu mühürler; başarılıysa blok hash'i (ve varsa imzası) yazılmış olur
+    fn seal(
+        &self,
+        block: &mut Block,
+        miner: &Miner,
+        cancel: &CancelToken,
+        report: &mut dyn FnMut(MiningProgress),
+    ) -> Result<MiningStats, SealError>;
+
+    // Bloğun mührü bu modelin kurallarına uyuyor mu? (Saklanan hash'in başlıkla tutarlılığı ayrıca kontrol edilir)
+    fn verify_seal(&self, block: &Block) -> Result<(), SealError>;
+
+    // En çok iş kuralında bloğun ağırlığı
+    fn block_weight(&self, block: &Block) -> u128;
+}
+
+// İş kanıtı (proof-of-work): hash, bits'ten çözülen hedefin altında olana kadar nonce aranır
+// Zorluk her RETARGET_INTERVAL blokta bir, blokların gerçekleşen süresine göre ayarlanır (Bitcoin tarzı)
+#[derive(Default)]
+pub struct ProofOfWork;
+
+impl Consensus for ProofOfWork {
+    fn name(&self) -> &'static str {
+        "proof-of-work"
+    }
+
+    // Her RETARGET_INTERVAL blokta bir, son pencerenin gerçekleşen süresi beklenen süreyle kıyaslanır
+    fn expected_bits(&self, genesis_bits: u32, ancestors: &[Block]) -> u32 {
+        let height = ancestors.len();
+        let Some(previous_block) = ancestors.last() else {
+            return genesis_bits; // Genesis blok başlangıç zorluğunu kullanır
+        };
+        if !height.is_multiple_of(RETARGET_INTERVAL) {
+            return previous_block.bits; // Ayarlama zamanı değilse zorluk değişmez
+        }
+        // Penceredeki ilk ve son blok arasında RETARGET_INTERVAL - 1 aralık vardır
+        let first_block = &ancestors[height - RETARGET_INTERVAL];
+        let actual_timespan = previous_block.timestamp.saturating_sub(first_block.timestamp);
+        let expected_timespan = (RETARGET_INTERVAL as u64 - 1) * TARGET_BLOCK_TIME;
+        retarget(previous_block.bits, actual_timespan, expected_timespan)
+    }
+
+    fn seal(
+        &self,
+        block: &mut Block,
+        miner: &Miner,
+        cancel: &CancelToken,
+        report: &mut dyn FnMut(MiningProgress),
+    ) -> Result<MiningStats, SealError> {
+        miner.mine_with(block, cancel, report).ok_or(SealError::Cancelled)
+    }
+
+    // Hash sayısal olarak bits'ten çözülen 256-bit hedeften küçük ya da eşit olmalıdır
+    fn verify_seal(&self, block: &Block) -> Result<(), SealError> {
+        if hash_meets_target(&block.hash, &bits_to_target(block.bits)) {
+            Ok(())
+        } else {
+            Err(SealError::InsufficientWork { bits: block.bits })
+        }
+    }
+
+    fn block_weight(&self, block: &Block) -> u128 {
+        block_work(block.bits)
+    }
+}
+
+// Yetki kanıtı bloğunun mührü: bloğu imzalayan yetkilinin açık anahtarı ve blok hash'inin imzası
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BlockSignature {
+    pub public_key: String, // Ed25519 açık anahtarı (hex)
+    pub signature: String,  // Blok hash'inin imzası (hex)
+}
+
+// Yetki kanıtı (proof-of-authority): blokları sabit bir yetkili listesi sırayla (round-robin) imzalar
+// h yüksekliğindeki bloğu authorities[h % n] adresinin anahtarı imzalamalıdır; hash araması yapılmaz, zorluk yoktur
+// Her blok aynı ağırlıktadır, yani en uzun geçerli zincir kazanır
+pub struct ProofOfAuthority {
+    authorities: Vec<String>, // Yetkililerin adresleri, imza sırasıyla
+    keys: Vec<SigningKey>,    // Bu düğümde yüklü olan yetkili anahtarları (sırası gelince bunlarla imzalanır)
+}
+
+impl ProofOfAuthority {
+    // Yetkili listesi boş olamaz; anahtarlar listedeki adreslerden herhangi birine ait olabilir
+    pub fn new(authorities: Vec<String>, keys: Vec<SigningKey>) -> ProofOfAuthority {
+        assert!(!authorities.is_empty(), "proof-of-authority needs at least one authority");
+        ProofOfAuthority { authorities, keys }
+    }
+
+    // Verilen yükseklikte imza sırası gelen yetkili
+    pub fn signer_for(&self, height: usize) -> &str {
+        &self.authorities[height % self.authorities.len()]
+    }
+}
+
+impl Consensus for ProofOfAuthority {
+    fn name(&self) -> &'static str {
+        "proof-of-authority"
+    }
+
+    fn expected_bits(&self, _genesis_bits: u32, _ancestors: &[Block]) -> u32 {
+        0
+    }
+
+    fn seal(
+        &self,
+        block: &mut Block,
+        _miner: &Miner,
+        _cancel: &CancelToken,
+        _report: &mut dyn FnMut(MiningProgress),
+    ) -> Result<MiningStats, SealError> {
+        let started = Instant::now();
+        let signer = self.signer_for(block.index as usize);
+        let Some(key) = self.keys.iter().find(|key| address_of(&key.verifying_key()) == signer) else {
+            return Err(SealError::NotInTurn { signer: signer.to_string() });
+        };
+        block.nonce = 0;
+        block.hash = block.calculate_hash();
+        block.signature = Some(BlockSignature {
+            public_key: to_hex(key.verifying_key().as_bytes()),
+            signature: to_hex(&key.sign(&block.hash).to_bytes()),
+        });
+        Ok(MiningStats { attempts: 0, elapsed: started.elapsed().max(Duration::from_nanos(1)) })
+    }
+
+    fn verify_seal(&self, block: &Block) -> Result<(), SealError> {
+        let seal = block.signature.as_ref().ok_or(SealError::MissingSignature)?;
+        let public_key = from_hex(&seal.public_key)
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+            .ok_or(SealError::BadSignature)?;
+        let expected = self.signer_for(block.index as usize);
+        let found = address_of(&public_key);
+        if found != expected {
+            return Err(SealError::WrongSigner { expected: expected.to_string(), found });
+        }
+        let signature = from_hex(&seal.signature)
+            .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+            .map(|bytes| Signature::from_bytes(&bytes))
+            .ok_or(SealError::BadSignature)?;
+        public_key.verify_strict(&block.hash, &signature).map_err(|_| SealError::BadSignature)
+    }
+
+    fn block_weight(&self, _block: &Block) -> u128 {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Blockchain;
+    use rand::rngs::OsRng;
+    use std::sync::Arc;
+
+    // Üç yetkilili bir ağ; bu düğümde sadece verilen sıralardaki anahtarlar yüklüdür
+    fn authorities(local: &[usize]) -> (ProofOfAuthority, Vec<SigningKey>) {
+        let keys: Vec<SigningKey> = (0..3).map(|_| SigningKey::generate(&mut OsRng)).collect();
+        let addresses = keys.iter().map(|key| address_of(&key.verifying_key())).collect();
+        let loaded = local.iter().map(|&i| keys[i].clone()).collect();
+        (ProofOfAuthority::new(addresses, loaded), keys)
+    }
+
+    fn seal_next(chain: &mut Blockchain<ProofOfAuthority>) -> Result<(), SealError> {
+        let mut block = chain.candidate_block("validator", Vec::new()).unwrap();
+        let consensus = Arc::clone(&chain.consensus);
+        consensus.seal(&mut block, &chain.miner, &CancelToken::default(), &mut |_| {})?;
+        chain.add_block(block).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn authorities_seal_in_round_robin_order() {
+        let (consensus, _) = authorities(&[0, 1, 2]);
+        let mut chain = Blockchain::with_consensus(Arc::new(consensus), 0);
+        for _ in 0..5 {
+            seal_next(&mut chain).unwrap();
+        }
+        assert!(chain.validate().is_valid());
+        assert_eq!(chain.total_work(), 5);
+        assert!(chain.blocks.iter().all(|block| block.bits == 0 && block.signature.is_some()));
+    }
+
+    #[test]
+    fn node_without_the_in_turn_key_cannot_seal() {
+        let (consensus, _) = authorities(&[0]);
+        let mut chain = Blockchain::with_consensus(Arc::new(consensus), 0);
+        seal_next(&mut chain).unwrap();
+        assert!(matches!(seal_next(&mut chain), Err(SealError::NotInTurn { .. })));
+    }
+
+    #[test]
+    fn out_of_turn_or_forged_signatures_are_rejected() {
+        let (consensus, keys) = authorities(&[0, 1, 2]);
+        let chain = Blockchain::with_consensus(Arc::new(consensus), 0);
+        let mut block = chain.candidate_block("validator", Vec::new()).unwrap();
+        chain.consensus.seal(&mut block, &chain.miner, &CancelToken::default(), &mut |_| {}).unwrap();
+        assert!(chain.consensus.verify_seal(&block).is_ok());
+
+        // Genesis'i sırası gelmeyen ikinci yetkili imzalarsa
+        let mut out_of_turn = block.clone();
+        out_of_turn.signature = Some(BlockSignature {
+            public_key: to_hex(keys[1].verifying_key().as_bytes()),
+            signature: to_hex(&keys[1].sign(&block.hash).to_bytes()),
+        });
+        assert!(matches!(chain.consensus.verify_seal(&out_of_turn), Err(SealError::WrongSigner { .. })));
+
+        // İmza başka bir hash'e aitse
+        let mut forged = block.clone();
+        forged.hash = [1; 32];
+        assert!(matches!(chain.consensus.verify_seal(&forged), Err(SealError::BadSignature)));
+
+        let mut unsigned = block;
+        unsigned.signature = None;
+        assert!(matches!(chain.consensus.verify_seal(&unsigned), Err(SealError::MissingSignature)));
+    }
+}
@@ -0,0 +1,203 @@
+// Bitcoin'deki "compact bits" gösterimi ve 256-bit hedef (target) hesaplamaları
+// Hash, büyük bir sayı (big-endian) olarak yorumlanır ve hedefe eşit ya da küçükse geçerlidir
+
+// 256-bit hedef değeri (big-endian byte dizisi, en anlamlı byte başta)
+pub type Target = [u8; 32];
+
+// Simülatörün varsayılan zorluğu: hash'in ilk byte'ı sıfır olmalı (~256 denemede bir blok)
+pub const DEFAULT_BITS: u32 = 0x2000ffff;
+
+// En kolay zorluk (Bitcoin regtest ile aynı): hash'lerin yaklaşık yarısı geçerli sayılır
+// Zorluk ayarlaması hedefi bundan daha kolay hale getiremez (Bitcoin'deki "pow limit")
+// Testlerde blokların milisaniyeler içinde bulunabilmesi için de kullanılır
+pub const EASIEST_BITS: u32 = 0x207fffff;
+
+// Kaç blokta bir zorluğun yeniden hesaplanacağı (Bitcoin'de 2016)
+pub const RETARGET_INTERVAL: usize = 5;
+
+// Hedeflenen bloklar arası süre, saniye cinsinden (Bitcoin'de 600)
+pub const TARGET_BLOCK_TIME: u64 = 10;
+
+// Bir ayarlamada hedef en fazla 4 kat büyüyüp küçülebilir
+const MAX_ADJUSTMENT_FACTOR: u64 = 4;
+
+// Compact bits değerini 256-bit hedefe çevirir
+// Üst 8 bit üs (exponent), alt 23 bit mantis: hedef = mantis * 256^(üs - 3)
+pub fn bits_to_target(bits: u32) -> Target {
+    let exponent = (bits >> 24) as i32;
+    let mantissa = bits & 0x007f_ffff; // İşaret biti (0x00800000) negatif hedef anlamına gelir, yok sayılır
+    let mut target = [0u8; 32];
+
+    // Mantisin üç byte'ı, üsse göre hedefin içinde doğru basamağa yerleştirilir
+    for (i, byte) in mantissa.to_be_bytes()[1..].iter().enumerate() {
+        let position = exponent - 1 - i as i32; // En anlamsız byte'tan itibaren basamak numarası
+        if (0..32).contains(&position) {
+            target[31 - position as usize] = *byte;
+        }
+    }
+    target
+}
+
+// 256-bit hedefi compact bits gösterimine çevirir (bits_to_target'ın tersi)
+// Mantis en fazla 3 byte taşır, bu yüzden alt basamaklardaki hassasiyet kaybolur
+pub fn target_to_bits(target: &Target) -> u32 {
+    let first = match target.iter().position(|b| *b != 0) {
+        Some(first) => first,
+        None => return 0,
+    };
+    let mut size = (32 - first) as u32; // Hedefin byte cinsinden uzunluğu
+    let mut mantissa = [0u8; 4];
+    for (i, byte) in target[first..].iter().take(3).enumerate() {
+        mantissa[i + 1] = *byte;
+    }
+    let mut mantissa = u32::from_be_bytes(mantissa); // Hedef 3 byte'tan kısaysa sağı sıfırla dolar
+    // En üst bit set ise değer negatif sanılmasın diye mantis bir byte sağa kaydırılır
+    if mantissa & 0x0080_0000 != 0 {
+        mantissa >>= 8;
+        size += 1;
+    }
+    (size << 24) | mantissa
+}
+
+// Yeni zorluğu hesaplar: hedef, gerçekleşen süre / beklenen süre oranında ölçeklenir
+// Bloklar hızlı geldiyse hedef küçülür (zorlaşır), yavaş geldiyse büyür (kolaylaşır)
+pub fn retarget(bits: u32, actual_timespan: u64, expected_timespan: u64) -> u32 {
+    let expected_timespan = expected_timespan.max(1);
+    let actual_timespan = actual_timespan.clamp(
+        (expected_timespan / MAX_ADJUSTMENT_FACTOR).max(1),
+        expected_timespan * MAX_ADJUSTMENT_FACTOR,
+    );
+    let target = scale(&bits_to_target(bits), actual_timespan, expected_timespan);
+    let limit = bits_to_target(EASIEST_BITS);
+    target_to_bits(if target > limit { &limit } else { &target })
+}
+
+// target * numerator / denominator işlemini 256-bit üzerinde yapar
+// Taşan değerler 256-bit'in en büyük değerine sabitlenir
+fn scale(target: &Target, numerator: u64, denominator: u64) -> Target {
+    // Çarpma: sağdan sola byte byte, eldeyle birlikte (sonuç 40 byte'a sığar)
+    let mut product = [0u8; 40];
+    let mut carry: u128 = 0;
+    for i in (0..40).rev() {
+        let digit = if i >= 8 { target[i - 8] as u128 } else { 0 };
+        let value = digit * numerator as u128 + carry;
+        product[i] = value as u8;
+        carry = value >> 8;
+    }
+
+    // Bölme: soldan sağa uzun bölme, kalan bir sonraki basamağa taşınır
+    let mut quotient = [0u8; 40];
+    let mut remainder: u128 = 0;
+    for (i, byte) in product.iter().enumerate() {
+        let value = (remainder << 8) | *byte as u128;
+        quotient[i] = (value / denominator as u128) as u8;
+        remainder = value % denominator as u128;
+    }
+
+    if quotient[..8].iter().any(|b| *b != 0) {
+        return [0xff; 32];
+    }
+    let mut result = [0u8; 32];
+    result.copy_from_slice(&quotient[8..]);
+    result
+}
+
+// Bir bloğun temsil ettiği iş: geçerli hash bulmak için ortalama deneme sayısı (≈ 2^256 / (hedef + 1))
+// Zincir seçiminde blok sayısı yerine bu değerlerin toplamı (birikmiş iş) karşılaştırılır
+pub fn block_work(bits: u32) -> u128 {
+    let target = bits_to_target(bits);
+    let Some(first) = target.iter().position(|b| *b != 0) else {
+        return u128::MAX; // Sıfır hedef hiçbir zaman sağlanamaz
+    };
+    let zeros = first as u32 * 8 + target[first].leading_zeros(); // Hedefin başındaki sıfır bitleri
+
+    // Hedefin ilk 1 bitinden başlayan 64 bitlik kısmı (mantis, 2^63 <= m < 2^64)
+    let mut top = [0u8; 16];
+    let available = (32 - first).min(16);
+    top[..available].copy_from_slice(&target[first..first + available]);
+    let mantissa = (u128::from_be_bytes(top) << target[first].leading_zeros()) >> 64;
+
+    // hedef ≈ m * 2^(192 - zeros) olduğundan iş = 2^(64 + zeros) / m = (2^127 / m) * 2^(zeros - 63)
+    let base = (1u128 << 127) / mantissa;
+    if zeros < 63 {
+        base >> (63 - zeros)
+    } else if zeros - 63 < base.leading_zeros() {
+        base << (zeros - 63)
+    } else {
+        u128::MAX
+    }
+}
+
+// Hash hedefe eşit ya da ondan küçükse zorluk koşulu sağlanmıştır
+// İki dizi de big-endian olduğundan byte byte karşılaştırma sayısal karşılaştırmayla aynıdır
+pub fn hash_meets_target(hash: &[u8; 32], target: &Target) -> bool {
+    hash <= target
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_bitcoin_genesis_bits() {
+        // 0x1d00ffff -> 0x00000000ffff0000...0000
+        let target = bits_to_target(0x1d00ffff);
+        assert_eq!(&target[..6], &[0x00, 0x00, 0x00, 0x00, 0xff, 0xff]);
+        assert!(target[6..].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn decodes_small_exponents() {
+        let target = bits_to_target(0x03123456);
+        assert_eq!(&target[29..], &[0x12, 0x34, 0x56]);
+        // Üs 3'ten küçükse mantis sağa kaydırılır
+        let target = bits_to_target(0x02123456);
+        assert_eq!(&target[30..], &[0x12, 0x34]);
+    }
+
+    #[test]
+    fn compares_hashes_numerically() {
+        let target = bits_to_target(DEFAULT_BITS);
+        let mut hash = [0xffu8; 32];
+        assert!(!hash_meets_target(&hash, &target));
+        hash[0] = 0x00;
+        assert!(!hash_meets_target(&hash, &target)); // 0x00ffff.. hedeften (0x00ffff00..) büyük
+        hash[2] = 0x00;
+        assert!(hash_meets_target(&hash, &target));
+    }
+
+    #[test]
+    fn encodes_targets_back_to_bits() {
+        for bits in [0x1d00ffff, DEFAULT_BITS, EASIEST_BITS, 0x03123456] {
+            assert_eq!(target_to_bits(&bits_to_target(bits)), bits);
+        }
+        // Mantisin en üst biti set olacaksa üs bir artırılır
+        let mut target = [0u8; 32];
+        target[31] = 0x80;
+        assert_eq!(target_to_bits(&target), 0x02008000);
+    }
+
+    #[test]
+    fn retarget_scales_with_block_speed() {
+        let easier = bits_to_target(retarget(DEFAULT_BITS, 80, 40));
+        let harder = bits_to_target(retarget(DEFAULT_BITS, 20, 40));
+        let unchanged = bits_to_target(retarget(DEFAULT_BITS, 40, 40));
+        assert!(harder < unchanged && unchanged < easier);
+        assert_eq!(unchanged, bits_to_target(DEFAULT_BITS));
+    }
+
+    #[test]
+    fn harder_targets_represent_more_work() {
+        assert_eq!(block_work(EASIEST_BITS), 2);
+        assert_eq!(block_work(0x1d00ffff), 4_295_032_833); // Bitcoin'de zorluk 1: ~2^32 hash
+        assert!(block_work(DEFAULT_BITS) > block_work(EASIEST_BITS));
+    }
+
+    #[test]
+    fn retarget_is_clamped() {
+        // 100 kat hızlı gelen bloklar hedefi en fazla 4 kat küçültür
+        assert_eq!(retarget(0x1d00ffff, 1, 400), retarget(0x1d00ffff, 100, 400));
+        // Hedef hiçbir zaman en kolay zorluğu aşamaz
+        assert_eq!(retarget(EASIEST_BITS, 1_000, 10), EASIEST_BITS);
+    }
+}
@@ -0,0 +1,191 @@
+// Zincir gezgini (explorer): ana zincir üzerinde indeksli sorgular
+// İndeksler bellekte tutulur ve add_block ile güncellenir; böylece sorgular tüm zinciri taramaz
+//   - hash -> yükseklik
+//   - adres -> adresin alıcı ya da gönderici olduğu işlemler (yükseklik, işlem sırası)
+// Yüksekliğe göre sorgu doğrudan blok dizisinden, zaman aralığı sorgusu ise ana zincirde zaman damgaları
+// azalmadığı için (add_block bunu garanti eder) ikili arama ile yapılır
+
+use std::collections::HashMap;
+
+use chrono::DateTime;
+
+use crate::consensus::Consensus;
+use crate::merkle::Hash;
+use crate::transaction::Transaction;
+use crate::{to_hex, Block, Blockchain};
+
+// Tablo görünümünde bir sayfadaki satır sayısı
+pub const PAGE_SIZE: usize = 10;
+
+// Blok tablosunun başlığı (satırlar block_row ile aynı genişliklerde yazılır)
+pub const BLOCK_TABLE_HEADER: &str = "Height  Hash              Time (UTC)           Txs  Bits";
+
+// Adres işlemleri tablosunun başlığı
+pub const TRANSACTION_TABLE_HEADER: &str = "Height  Pos  Txid              Received  Sent";
+
+// Bir işlemin ana zincirdeki yeri
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TxLocation {
+    pub height: usize,   // İşlemi içeren bloğun yüksekliği
+    pub position: usize, // İşlemin bloktaki sırası
+}
+
+// Ana zincirin indeksleri
+#[derive(Clone, Default)]
+pub struct ChainIndex {
+    by_hash: HashMap<Hash, usize>,                // Blok hash'i -> yükseklik
+    by_address: HashMap<String, Vec<TxLocation>>, // Adres -> işlemler (zincirdeki sırasıyla)
+}
+
+impl ChainIndex {
+    // Blok dizisinin tamamından indeks kurar (diskten yüklenen ya da elle değiştirilen zincirler için)
+    pub fn build(blocks: &[Block]) -> ChainIndex {
+        let mut index = ChainIndex::default();
+        for (height, block) in blocks.iter().enumerate() {
+            index.insert(height, block);
+        }
+        index
+    }
+
+    // Ana zincirin ucuna eklenen bloğu indekslere ekler
+    pub fn insert(&mut self, height: usize, block: &Block) {
+        self.by_hash.insert(block.hash, height);
+        for (position, transaction) in block.transactions.iter().enumerate() {
+            let location = TxLocation { height, position };
+            let receivers = transaction.outputs.iter().map(|output| output.address.clone());
+            let senders = transaction.inputs.iter().filter_map(|input| input.owner());
+            for address in receivers.chain(senders) {
+                let locations = self.by_address.entry(address).or_default();
+                // Aynı işlemde adres birden çok kez geçebilir; işlem bir kez listelenir
+                if locations.last() != Some(&location) {
+                    locations.push(location);
+                }
+            }
+        }
+    }
+}
+
+impl<C: Consensus> Blockchain<C> {
+    // İndeksleri bloklardan yeniden kurar
+    pub fn reindex(&mut self) {
+        self.index = ChainIndex::build(&self.blocks);
+    }
+
+    // Ana zincirde hash'i verilen blok
+    pub fn block_by_hash(&self, hash: &Hash) -> Option<&Block> {
+        self.main_height_of(hash).map(|height| &self.blocks[height])
+    }
+
+    // Hash'i verilen bloğun ana zincirdeki yüksekliği
+    pub fn main_height_of(&self, hash: &Hash) -> Option<usize> {
+        self.index.by_hash.get(hash).copied()
+    }
+
+    // Ana zincirde verilen yükseklikteki blok
+    pub fn block_at(&self, height: usize) -> Option<&Block> {
+        self.blocks.get(height)
+    }
+
+    // Adresin alıcı ya da gönderici olduğu işlemler, zincirdeki sırasıyla
+    pub fn transactions_of(&self, address: &str) -> Vec<(TxLocation, &Transaction)> {
+        self.index
+            .by_address
+            .get(address)
+            .into_iter()
+            .flatten()
+            .map(|&location| (location, &self.blocks[location.height].transactions[location.position]))
+            .collect()
+    }
+
+    // Zaman damgası [from, to] aralığında olan bloklar
+    pub fn blocks_between(&self, from: u64, to: u64) -> &[Block] {
+        let start = self.blocks.partition_point(|block| block.timestamp < from);
+        let end = self.blocks.partition_point(|block| block.timestamp <= to);
+        &self.blocks[start..end.max(start)]
+    }
+}
+
+// Bloğun tablo satırı; hash kısaltılır
+pub fn block_row(block: &Block) -> String {
+    format!(
+        "{:>6}  {:<16}  {:<19}  {:>3}  {:#010x}",
+        block.index,
+        &to_hex(&block.hash)[..16],
+        format_time(block.timestamp),
+        block.transactions.len(),
+        block.bits
+    )
+}
+
+// Adresin bir işlemdeki payı: adrese gelen miktar ve adresin işlemde harcama yapıp yapmadığı
+pub fn transaction_row(location: TxLocation, transaction: &Transaction, address: &str) -> String {
+    let received: u64 = transaction
+        .outputs
+        .iter()
+        .filter(|output| output.address == address)
+        .map(|output| output.amount)
+        .sum();
+    let sent = transaction.inputs.iter().any(|input| input.owner().as_deref() == Some(address));
+    format!(
+        "{:>6}  {:>3}  {:<16}  {:>8}  {}",
+        location.height,
+        location.position,
+        &transaction.txid()[..16],
+        received,
+        if sent { "yes" } else { "no" }
+    )
+}
+
+// Unix zaman damgasını okunur UTC tarihe çevirir
+pub fn format_time(timestamp: u64) -> String {
+    DateTime::from_timestamp(timestamp as i64, 0)
+        .map_or_else(|| timestamp.to_string(), |time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::EASIEST_BITS;
+    use crate::tests::{funded_chain, mine, mine_block};
+    use crate::transaction::TxOutput;
+
+    #[test]
+    fn indexes_follow_added_blocks() {
+        let (mut blockchain, key, alice) = funded_chain(1);
+        let payment = vec![TxOutput { amount: 5, address: String::from("bob"), script: None }];
+        let transfer = blockchain.utxos.build_transaction(&key, payment, 0).unwrap();
+        mine_block(&mut blockchain, "miner", vec![transfer]).unwrap();
+
+        let tip = blockchain.blocks[1].hash;
+        assert_eq!(blockchain.block_by_hash(&tip).unwrap().index, 1);
+        assert!(blockchain.block_by_hash(&[7; 32]).is_none());
+        assert_eq!(blockchain.block_at(0).unwrap().hash, blockchain.blocks[0].hash);
+
+        // Alice coinbase'i aldı, sonra bob'a ödeme yaptı (ve para üstünü aldı)
+        let locations: Vec<TxLocation> = blockchain.transactions_of(&alice).into_iter().map(|(at, _)| at).collect();
+        assert_eq!(locations, vec![TxLocation { height: 0, position: 0 }, TxLocation { height: 1, position: 1 }]);
+        assert_eq!(blockchain.transactions_of("bob").len(), 1);
+        assert!(blockchain.transactions_of("nobody").is_empty());
+
+        // Elle kurulan indeks add_block'un güncellediğiyle aynı olmalı
+        let rebuilt = ChainIndex::build(&blockchain.blocks);
+        assert_eq!(rebuilt.by_hash, blockchain.index.by_hash);
+        assert_eq!(rebuilt.by_address, blockchain.index.by_address);
+    }
+
+    #[test]
+    fn time_range_query_uses_timestamps() {
+        let mut blockchain = Blockchain::with_difficulty(EASIEST_BITS);
+        for timestamp in [100, 200, 200, 300] {
+            let mut block = blockchain.candidate_block("miner", Vec::new()).unwrap();
+            block.timestamp = timestamp;
+            mine(&mut block);
+            blockchain.add_block(block).unwrap();
+        }
+        let heights = |from, to| blockchain.blocks_between(from, to).iter().map(|b| b.index).collect::<Vec<_>>();
+        assert_eq!(heights(150, 250), vec![1, 2]);
+        assert_eq!(heights(0, 100), vec![0]);
+        assert_eq!(heights(301, 400), Vec::<u32>::new());
+        assert_eq!(heights(250, 150), Vec::<u32>::new());
+    }
+}
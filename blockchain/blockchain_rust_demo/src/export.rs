@@ -0,0 +1,242 @@
+// Zincirin dışa ve içe aktarılması (JSON ve CSV)
+// Zincir dosyası (JSON-lines) programın kendi deposudur; dışa aktarma ise zinciri başka çalışmalara ve araçlara
+// (tablo programları, betikler) taşımak içindir
+//   - JSON: blokların tamamı tek bir dizi olarak, okunur biçimde
+//   - CSV: blok başına bir satır; işlemler "data" sütununda JSON olarak durur
+// İçe aktarılan her bloğun hash'i başlıktan yeniden hesaplanır, ardından zincir baştan sona doğrulanır
+
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::consensus::{BlockSignature, Consensus};
+use crate::merkle::Hash;
+use crate::validation::ValidationReport;
+use crate::{Block, Blockchain};
+
+// Desteklenen dosya biçimleri
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChainFormat {
+    Json,
+    Csv,
+}
+
+impl ChainFormat {
+    // Biçim dosya uzantısından anlaşılır (.json ya da .csv)
+    pub fn from_path(path: &Path) -> Option<ChainFormat> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(ChainFormat::Json),
+            "csv" => Some(ChainFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+// İçe aktarma başarısız olduğunda dönen hatalar
+#[derive(Debug)]
+pub enum ImportError {
+    Io(io::Error),                  // Dosya okunamadı
+    Malformed(String),              // Dosya beklenen biçimde değil
+    HashMismatch { index: usize },  // Bloğun hash'i başlığından yeniden hesaplananla uyuşmuyor
+    InvalidChain(ValidationReport), // Bloklar zincir olarak geçersiz
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(err) => write!(f, "I/O error: {}", err),
+            ImportError::Malformed(reason) => write!(f, "malformed file: {}", reason),
+            ImportError::HashMismatch { index } => {
+                write!(f, "block {} does not hash to its stored hash", index)
+            }
+            ImportError::InvalidChain(report) => write!(f, "imported blocks do not form a valid chain: {}", report),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<io::Error> for ImportError {
+    fn from(err: io::Error) -> Self {
+        ImportError::Io(err)
+    }
+}
+
+// CSV'deki bir satır: başlık alanları, mühür ve işlemler
+#[derive(Serialize, Deserialize)]
+struct BlockRow {
+    index: u32,
+    version: u32,
+    #[serde(with = "crate::header::hex_hash")]
+    previous_hash: Hash,
+    #[serde(with = "crate::header::hex_hash")]
+    hash: Hash,
+    #[serde(with = "crate::header::hex_hash")]
+    merkle_root: Hash,
+    timestamp: u64,
+    bits: u32,
+    nonce: u32,
+    signer: Option<String>,    // Yetki kanıtında imzalayanın açık anahtarı (iş kanıtında boş)
+    signature: Option<String>, // Yetki kanıtında blok hash'inin imzası (iş kanıtında boş)
+    data: String,              // İşlemler (JSON dizisi)
+}
+
+impl BlockRow {
+    fn from_block(block: &Block) -> io::Result<BlockRow> {
+        Ok(BlockRow {
+            index: block.index,
+            version: block.version,
+            previous_hash: block.previous_block_hash,
+            hash: block.hash,
+            merkle_root: block.merkle_root,
+            timestamp: block.timestamp,
+            bits: block.bits,
+            nonce: block.nonce,
+            signer: block.signature.as_ref().map(|seal| seal.public_key.clone()),
+            signature: block.signature.as_ref().map(|seal| seal.signature.clone()),
+            data: serde_json::to_string(&block.transactions)?,
+        })
+    }
+
+    fn into_block(self) -> Result<Block, String> {
+        let transactions = serde_json::from_str(&self.data).map_err(|err| format!("block {}: {}", self.index, err))?;
+        let signature = match (self.signer, self.signature) {
+            (Some(public_key), Some(signature)) => Some(BlockSignature { public_key, signature }),
+            (None, None) => None,
+            _ => return Err(format!("block {}: signer and signature must be given together", self.index)),
+        };
+        Ok(Block {
+            index: self.index,
+            version: self.version,
+            previous_block_hash: self.previous_hash,
+            timestamp: self.timestamp,
+            merkle_root: self.merkle_root,
+            transactions,
+            bits: self.bits,
+            nonce: self.nonce,
+            hash: self.hash,
+            signature,
+        })
+    }
+}
+
+impl<C: Consensus> Blockchain<C> {
+    // Ana zinciri verilen biçimde dosyaya yazar
+    pub fn export(&self, path: &Path, format: ChainFormat) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        match format {
+            ChainFormat::Json => serde_json::to_writer_pretty(&mut writer, &self.blocks)?,
+            ChainFormat::Csv => {
+                let mut csv = csv::Writer::from_writer(&mut writer);
+                for block in &self.blocks {
+                    csv.serialize(BlockRow::from_block(block)?).map_err(io::Error::other)?;
+                }
+                csv.flush()?;
+            }
+        }
+        writer.flush()
+    }
+}
+
+// Dosyadaki zinciri okur, hash'leri yeniden hesaplar ve zinciri verilen uzlaşma kurallarıyla doğrular
+// Zincir geçerliyse defteri ve indeksleri kurulmuş olarak döner
+pub fn import<C: Consensus>(
+    path: &Path,
+    format: ChainFormat,
+    consensus: Arc<C>,
+    bits: u32,
+) -> Result<Blockchain<C>, ImportError> {
+    let file = File::open(path)?;
+    let blocks: Vec<Block> = match format {
+        ChainFormat::Json => serde_json::from_reader(io::BufReader::new(file))
+            .map_err(|err| ImportError::Malformed(err.to_string()))?,
+        ChainFormat::Csv => csv::Reader::from_reader(file)
+            .deserialize::<BlockRow>()
+            .map(|row| {
+                let row = row.map_err(|err| ImportError::Malformed(err.to_string()))?;
+                row.into_block().map_err(ImportError::Malformed)
+            })
+            .collect::<Result<_, _>>()?,
+    };
+    // Hash'i tutmayan blok, zincir kurallarına bakılmadan reddedilir (dosya elle değiştirilmiş ya da bozulmuş)
+    if let Some(index) = blocks.iter().position(|block| block.hash != block.calculate_hash()) {
+        return Err(ImportError::HashMismatch { index });
+    }
+    Blockchain::from_blocks(consensus, bits, blocks).map_err(ImportError::InvalidChain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::ProofOfWork;
+    use crate::difficulty::EASIEST_BITS;
+    use crate::tests::{funded_chain, mine, mine_block, temp_path};
+    use crate::transaction::TxOutput;
+    use std::fs;
+
+    fn chain_with_payment() -> Blockchain {
+        let (mut blockchain, key, miner) = funded_chain(1);
+        let payment = vec![TxOutput { amount: 5, address: String::from("bob"), script: None }];
+        let transfer = blockchain.utxos.build_transaction(&key, payment, 1).unwrap();
+        mine_block(&mut blockchain, &miner, vec![transfer]).unwrap();
+        blockchain
+    }
+
+    fn reimport(path: &Path) -> Result<Blockchain, ImportError> {
+        let format = ChainFormat::from_path(path).unwrap();
+        import(path, format, Arc::new(ProofOfWork), EASIEST_BITS)
+    }
+
+    #[test]
+    fn json_and_csv_round_trip() {
+        let blockchain = chain_with_payment();
+        for name in ["chain.json", "chain.csv"] {
+            let path = temp_path(&format!("export-{}", name));
+            blockchain.export(&path, ChainFormat::from_path(&path).unwrap()).unwrap();
+            let imported = reimport(&path).unwrap();
+            assert_eq!(
+                serde_json::to_string(&imported.blocks).unwrap(),
+                serde_json::to_string(&blockchain.blocks).unwrap()
+            );
+            assert_eq!(imported.balance_of("bob"), 5);
+            assert!(imported.block_by_hash(&blockchain.blocks[1].hash).is_some());
+            fs::remove_file(&path).unwrap();
+        }
+        assert_eq!(ChainFormat::from_path(Path::new("chain.txt")), None);
+    }
+
+    #[test]
+    fn edited_csv_is_rejected() {
+        let blockchain = chain_with_payment();
+        let path = temp_path("export-edited.csv");
+        blockchain.export(&path, ChainFormat::Csv).unwrap();
+
+        // Nonce değiştirilirse başlığın hash'i artık saklanan hash değildir
+        let contents = fs::read_to_string(&path).unwrap();
+        let nonce = format!(",{},", blockchain.blocks[1].nonce);
+        let row = contents.lines().nth(2).unwrap();
+        let edited = row.replacen(&nonce, &format!(",{},", blockchain.blocks[1].nonce + 1), 1);
+        fs::write(&path, contents.replace(row, &edited)).unwrap();
+        assert!(matches!(reimport(&path), Err(ImportError::HashMismatch { index: 1 })));
+
+        // İşlem verisi değişirse hash'ler tutar ama zincir doğrulaması Merkle kökünü yakalar
+        fs::write(&path, contents.replace("\"\"amount\"\":5,", "\"\"amount\"\":50,")).unwrap();
+        assert!(matches!(reimport(&path), Err(ImportError::InvalidChain(_))));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn remined_but_unlinked_json_is_rejected() {
+        let mut blockchain = chain_with_payment();
+        blockchain.blocks[1].previous_block_hash = [9; 32];
+        mine(&mut blockchain.blocks[1]); // Hash tutar, bağlantı kopuk
+        let path = temp_path("export-unlinked.json");
+        blockchain.export(&path, ChainFormat::Json).unwrap();
+        assert!(matches!(reimport(&path), Err(ImportError::InvalidChain(_))));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,452 @@
+// Çatallanmalar (fork) ve en çok iş (most-work) kuralıyla zincir seçimi
+// Ana zincir dışında kalan bloklar hash'lerine göre bir ağaçta tutulur
+// Bir yan dalın birikmiş işi ana zincirinkini geçerse zincir yeniden düzenlenir (reorganization)
+
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+
+use crate::consensus::Consensus;
+use crate::header::ZERO_HASH;
+use crate::merkle::Hash;
+use crate::{to_hex, Block, BlockError, Blockchain};
+
+// Bekletilen en fazla yetim blok sayısı; eşler sınırsız yetim göndererek belleği dolduramaz
+const MAX_ORPHANS: usize = 100;
+
+// Tutulan en fazla yan dal bloğu sayısı; eşler ana zincirden daha hafif sınırsız dal göndererek belleği dolduramaz
+const MAX_SIDE_BLOCKS: usize = 100;
+
+// Ana zincirin ucundan bu kadar bloktan daha aşağıda kalan yan bloklar atılır (o derinlikte bir yeniden düzenleme beklenmez)
+const MAX_SIDE_DEPTH: usize = 100;
+
+// Ana zincir dışındaki bloklar
+#[derive(Clone, Default)]
+pub struct BlockTree {
+    side_blocks: HashMap<Hash, Block>,     // Ebeveyni bilinen ama ana zincirde olmayan bloklar (hash -> blok)
+    orphans: HashMap<Hash, Vec<Block>>,    // Ebeveyni henüz gelmemiş bloklar (eksik ebeveyn hash'i -> bloklar)
+}
+
+impl BlockTree {
+    pub fn orphan_count(&self) -> usize {
+        self.orphans.values().map(Vec::len).sum()
+    }
+
+    pub fn side_block_count(&self) -> usize {
+        self.side_blocks.len()
+    }
+
+    // Üzerine başka yan blok kurulmamış en alçak yan bloğu atar (korunan blok hariç); atacak blok yoksa false döner
+    fn evict_lowest_leaf(&mut self, keep: &Hash) -> bool {
+        let lowest = self
+            .side_blocks
+            .values()
+            .filter(|block| block.hash != *keep)
+            .filter(|block| !self.side_blocks.values().any(|child| child.previous_block_hash == block.hash))
+            .min_by_key(|block| block.index)
+            .map(|block| block.hash);
+        lowest.is_some_and(|hash| self.side_blocks.remove(&hash).is_some())
+    }
+}
+
+// Gelen bir bloğun zincirde yol açtığı değişiklik
+pub enum ChainEvent {
+    Extended { hash: Hash },                                       // Blok ana zincirin ucuna eklendi
+    SideBranch { hash: Hash },                                     // Blok yan dala eklendi, ana zincir değişmedi
+    Orphan { hash: Hash, missing_parent: Hash },                   // Ebeveyn bilinmiyor, blok bekletiliyor
+    Reorganized { disconnected: Vec<Hash>, connected: Vec<Hash> }, // Daha ağır dal ana zincir oldu
+}
+
+impl Display for ChainEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainEvent::Extended { hash } => write!(f, "block {} extended the main chain", to_hex(hash)),
+            ChainEvent::SideBranch { hash } => write!(f, "block {} stored on a side branch", to_hex(hash)),
+            ChainEvent::Orphan { hash, missing_parent } => {
+                write!(f, "block {} is an orphan (waiting for parent {})", to_hex(hash), to_hex(missing_parent))
+            }
+            ChainEvent::Reorganized { disconnected, connected } => {
+                writeln!(f, "chain reorganized: {} block(s) disconnected, {} connected", disconnected.len(), connected.len())?;
+                for hash in disconnected {
+                    writeln!(f, "  - {}", to_hex(hash))?;
+                }
+                for hash in connected {
+                    writeln!(f, "  + {}", to_hex(hash))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<C: Consensus> Blockchain<C> {
+    // Ana zincirin birikmiş işi (blok ağırlıklarını uzlaşma motoru belirler)
+    pub fn total_work(&self) -> u128 {
+        self.blocks.iter().map(|block| self.consensus.block_weight(block)).fold(0, u128::saturating_add)
+    }
+
+    // Blok daha önce görülmüş mü? (ana zincir, yan dallar ya da yetimler)
+    pub fn contains(&self, hash: &Hash) -> bool {
+        self.main_height_of(hash).is_some()
+            || self.tree.side_blocks.contains_key(hash)
+            || self.tree.orphans.values().flatten().any(|block| block.hash == *hash)
+    }
+
+    // Ağdan ya da madenciden gelen bir bloğu işler; zincirde olan değişiklikleri sırasıyla döndürür
+    // Blok kendi başına geçersizse (hash, mühür, Merkle kökü) hiçbir yere kaydedilmez
+    pub fn receive_block(&mut self, block: Block) -> Result<Vec<ChainEvent>, BlockError> {
+        if self.contains(&block.hash) {
+            return Err(BlockError::Duplicate);
+        }
+        if block.hash != block.calculate_hash() {
+            return Err(BlockError::InvalidHash);
+        }
+        self.consensus.verify_seal(&block)?;
+        if block.merkle_root != block.calculate_merkle_root() {
+            return Err(BlockError::MerkleRootMismatch);
+        }
+
+        // Ebeveyni bilinmeyen blok, ebeveyni gelene kadar yetim olarak bekletilir
+        let parent = block.previous_block_hash;
+        let is_genesis = block.index == 0 && parent == ZERO_HASH;
+        if !is_genesis && self.main_height_of(&parent).is_none() && !self.tree.side_blocks.contains_key(&parent) {
+            let event = ChainEvent::Orphan { hash: block.hash, missing_parent: parent };
+            // Sınır dolduysa aynı ebeveyni bekleyen yetimlerden bir grup atılır (gerekirse eşten yeniden istenir)
+            if self.tree.orphan_count() >= MAX_ORPHANS {
+                let evicted = self.tree.orphans.keys().next().copied();
+                if let Some(evicted) = evicted {
+                    self.tree.orphans.remove(&evicted);
+                }
+            }
+            self.tree.orphans.entry(parent).or_default().push(block);
+            return Ok(vec![event]);
+        }
+
+        let hash = block.hash;
+        let mut events = vec![self.connect(block)?];
+
+        // Yeni blok, bekleyen yetimlerin ebeveyni olabilir; zincirleme olarak bağlanırlar
+        let mut ready = vec![hash];
+        while let Some(parent) = ready.pop() {
+            for orphan in self.tree.orphans.remove(&parent).unwrap_or_default() {
+                let orphan_hash = orphan.hash;
+                if let Ok(event) = self.connect(orphan) {
+                    events.push(event);
+                    ready.push(orphan_hash);
+                } // Geçersiz yetim sessizce atılır
+            }
+        }
+        Ok(events)
+    }
+
+    // Ebeveyni bilinen bloğu ana zincire ya da yan dala bağlar
+    fn connect(&mut self, block: Block) -> Result<ChainEvent, BlockError> {
+        let tip_hash = self.blocks.last().map_or(ZERO_HASH, |tip| tip.hash);
+        if block.previous_block_hash == tip_hash {
+            let hash = block.hash;
+            self.add_block(block)?;
+            self.prune_side_blocks();
+            return Ok(ChainEvent::Extended { hash });
+        }
+
+        // Yan blok da kendi dalının zorluk takvimine ve zaman sırasına uymalı
+        let (shared, branch) = self.side_branch(&block.previous_block_hash);
+        let ancestors: Vec<Block> = self.blocks[..shared].iter().cloned().chain(branch).collect();
+        if block.bits != self.consensus.expected_bits(self.bits, &ancestors) {
+            return Err(BlockError::WrongDifficulty);
+        }
+        if ancestors.last().is_some_and(|parent| block.timestamp < parent.timestamp) {
+            return Err(BlockError::NonMonotonicTimestamp);
+        }
+        // Sınır dolduysa başka bir dalın en alçak ucu atılır; yeni bloğun dalı bölünmez
+        if self.tree.side_blocks.len() >= MAX_SIDE_BLOCKS && !self.tree.evict_lowest_leaf(&block.previous_block_hash) {
+            return Err(BlockError::TooManySideBlocks);
+        }
+
+        let hash = block.hash;
+        self.tree.side_blocks.insert(hash, block);
+        if self.branch_work(&hash) > self.total_work() {
+            let event = self.reorganize(&hash)?;
+            self.prune_side_blocks();
+            Ok(event)
+        } else {
+            Ok(ChainEvent::SideBranch { hash })
+        }
+    }
+
+    // Ucu verilen yan dalın ana zincirden ayrıldığı yükseklik ve dalın blokları (eskiden yeniye)
+    // Uç ana zincirdeyse dal boştur ve ayrılma noktası ucun hemen üstüdür
+    fn side_branch(&self, tip: &Hash) -> (usize, Vec<Block>) {
+        let mut branch = Vec::new();
+        let mut cursor = *tip;
+        while let Some(block) = self.tree.side_blocks.get(&cursor) {
+            cursor = block.previous_block_hash;
+            branch.push(block.clone());
+        }
+        branch.reverse();
+        (self.main_height_of(&cursor).map_or(0, |height| height + 1), branch)
+    }
+
+    // Ana zincirin ucundan MAX_SIDE_DEPTH bloktan daha aşağıda kalan yan bloklar atılır
+    // Ebeveyni atılan yan bloklar da (artık hiçbir zincire bağlanamadıkları için) onlarla birlikte gider
+    fn prune_side_blocks(&mut self) {
+        let height = self.blocks.len();
+        self.tree.side_blocks.retain(|_, block| block.index as usize + MAX_SIDE_DEPTH >= height);
+        loop {
+            let dangling: Vec<Hash> = self
+                .tree
+                .side_blocks
+                .values()
+                .filter(|block| {
+                    let parent = &block.previous_block_hash;
+                    *parent != ZERO_HASH
+                        && self.main_height_of(parent).is_none()
+                        && !self.tree.side_blocks.contains_key(parent)
+                })
+                .map(|block| block.hash)
+                .collect();
+            if dangling.is_empty() {
+                break;
+            }
+            for hash in dangling {
+                self.tree.side_blocks.remove(&hash);
+            }
+        }
+    }
+
+    // Yan daldaki bloğun ucunda olduğu zincirin birikmiş işi
+    fn branch_work(&self, tip: &Hash) -> u128 {
+        let mut work: u128 = 0;
+        let mut cursor = tip;
+        while let Some(block) = self.tree.side_blocks.get(cursor) {
+            work = work.saturating_add(self.consensus.block_weight(block));
+            cursor = &block.previous_block_hash;
+        }
+        // Dalın ayrıldığı noktaya kadar ana zincirin işi eklenir
+        let shared = self.main_height_of(cursor).map_or(0, |height| height + 1);
+        self.blocks[..shared].iter().map(|block| self.consensus.block_weight(block)).fold(work, u128::saturating_add)
+    }
+
+    // Ucu verilen yan dalı ana zincir yapar
+    // Dal tamamen yeniden doğrulanır (zorluk takvimi, defter); geçersizse ana zincir değişmez
+    fn reorganize(&mut self, tip: &Hash) -> Result<ChainEvent, BlockError> {
+        let (shared, branch) = self.side_branch(tip);
+
+        // Ortak kısım ve yeni dal boş bir zincir üzerine tek tek eklenerek doğrulanır
+        let mut candidate = Blockchain::with_consensus(Arc::clone(&self.consensus), self.bits);
+        for block in &self.blocks[..shared] {
+            candidate.add_block(block.clone())?;
+        }
+        for (i, block) in branch.iter().enumerate() {
+            if let Err(err) = candidate.add_block(block.clone()) {
+                // Geçersiz blok ve ondan türeyen bloklar ağaçtan atılır
+                for invalid in &branch[i..] {
+                    self.tree.side_blocks.remove(&invalid.hash);
+                }
+                return Err(err);
+            }
+        }
+
+        // Ana zincirden çıkan bloklar yan dala taşınır, yeni dalın blokları ana zincire geçer
+        let disconnected: Vec<Block> = self.blocks.drain(shared..).collect();
+        let connected: Vec<Hash> = branch.iter().map(|block| block.hash).collect();
+        for hash in &connected {
+            self.tree.side_blocks.remove(hash);
+        }
+        let disconnected: Vec<Hash> = disconnected
+            .into_iter()
+            .map(|block| {
+                let hash = block.hash;
+                self.tree.side_blocks.insert(hash, block);
+                hash
+            })
+            .collect();
+        self.blocks = candidate.blocks;
+        self.index = candidate.index;
+        self.utxos = candidate.utxos;
+        // Ana zincirden çıkan blokların işlemleri yeni zincirde hâlâ geçerliyse havuza geri döner
+        let transactions = disconnected
+            .iter()
+            .flat_map(|hash| self.tree.side_blocks[hash].transactions[1..].to_vec())
+            .collect();
+        self.mempool.restore(transactions, &self.utxos, self.clock.now());
+        Ok(ChainEvent::Reorganized { disconnected, connected })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::{self, EASIEST_BITS};
+    use crate::tests::{chain_with, funded_chain, mine, mine_block};
+    use crate::transaction::TxOutput;
+    use ed25519_dalek::SigningKey;
+    use rand::rngs::OsRng;
+
+    // Ana zincirin ilk `height` bloğunu paylaşan ayrı bir zincir (rakip madencinin görüşü)
+    fn fork_of(blockchain: &Blockchain, height: usize) -> Blockchain {
+        let mut fork = Blockchain::with_difficulty(blockchain.bits);
+        for block in &blockchain.blocks[..height] {
+            fork.add_block(block.clone()).unwrap();
+        }
+        fork
+    }
+
+    #[test]
+    fn heavier_branch_triggers_reorganization() {
+        let mut honest = chain_with(3, "honest");
+        let mut rival = fork_of(&honest, 1);
+        for _ in 0..3 {
+            mine_block(&mut rival, "rival", Vec::new()).unwrap();
+        }
+
+        // Rakip dalın ilk iki bloğu ana zinciri geçemez
+        let events = honest.receive_block(rival.blocks[1].clone()).unwrap();
+        assert!(matches!(events[..], [ChainEvent::SideBranch { .. }]));
+        let events = honest.receive_block(rival.blocks[2].clone()).unwrap();
+        assert!(matches!(events[..], [ChainEvent::SideBranch { .. }]));
+
+        // Üçüncü blokla rakip dal daha çok iş içerir
+        let events = honest.receive_block(rival.blocks[3].clone()).unwrap();
+        let [ChainEvent::Reorganized { disconnected, connected }] = &events[..] else {
+            panic!("expected a reorganization");
+        };
+        assert_eq!(disconnected.len(), 2);
+        assert_eq!(connected.len(), 3);
+        assert_eq!(honest.blocks.last().unwrap().hash, rival.blocks[3].hash);
+        assert_eq!(honest.balance_of("rival"), 3 * crate::utxo::BLOCK_REWARD);
+        assert_eq!(honest.balance_of("honest"), crate::utxo::BLOCK_REWARD);
+        assert!(honest.validate().is_valid());
+    }
+
+    #[test]
+    fn reorganization_returns_disconnected_transactions_to_mempool() {
+        let (mut honest, key, _) = funded_chain(1);
+        let mut rival = fork_of(&honest, 1);
+        let bob = SigningKey::generate(&mut OsRng);
+        let bob_address = crate::wallet::address_of(&bob.verifying_key());
+        let payment = vec![TxOutput { amount: 5, address: bob_address.clone(), script: None }];
+        let transaction = honest.utxos.build_transaction(&key, payment, 1).unwrap();
+        mine_block(&mut honest, "honest", vec![transaction.clone()]).unwrap();
+        // Havuzda, bloğa girmiş ödemenin çıktısını harcayan bir işlem bekler
+        let spend = vec![TxOutput { amount: 3, address: String::from("carol"), script: None }];
+        let child = honest.utxos.build_transaction(&bob, spend, 1).unwrap();
+        honest.submit_transaction(child.clone()).unwrap();
+        for _ in 0..2 {
+            mine_block(&mut rival, "rival", Vec::new()).unwrap();
+        }
+
+        honest.receive_block(rival.blocks[1].clone()).unwrap();
+        honest.receive_block(rival.blocks[2].clone()).unwrap();
+        // Ödeme yeni ana zincirde yok; bir sonraki blok için tekrar havuzda bekler
+        // Ona bağlı işlem de atılmaz, ödemenin arkasında sırasını korur
+        assert_eq!(honest.balance_of(&bob_address), 0);
+        let txids: Vec<String> = honest.mempool.entries().iter().map(|entry| entry.transaction.txid()).collect();
+        assert_eq!(txids, vec![transaction.txid(), child.txid()]);
+    }
+
+    #[test]
+    fn orphans_connect_when_parent_arrives() {
+        let source = chain_with(3, "miner");
+        let mut blockchain = fork_of(&source, 1);
+        let events = blockchain.receive_block(source.blocks[2].clone()).unwrap();
+        assert!(matches!(events[..], [ChainEvent::Orphan { .. }]));
+        assert_eq!(blockchain.tree.orphan_count(), 1);
+
+        let events = blockchain.receive_block(source.blocks[1].clone()).unwrap();
+        assert!(matches!(events[..], [ChainEvent::Extended { .. }, ChainEvent::Extended { .. }]));
+        assert_eq!(blockchain.blocks.len(), 3);
+        assert_eq!(blockchain.tree.orphan_count(), 0);
+        assert!(matches!(blockchain.receive_block(source.blocks[2].clone()), Err(BlockError::Duplicate)));
+    }
+
+    #[test]
+    fn orphan_pool_is_capped() {
+        let source = chain_with(MAX_ORPHANS + 2, "miner");
+        let mut blockchain = fork_of(&source, 1);
+        // Ebeveyni (blok 1) hiç gelmeyen bloklar
+        for block in &source.blocks[2..] {
+            blockchain.receive_block(block.clone()).unwrap();
+        }
+        assert_eq!(blockchain.tree.orphan_count(), MAX_ORPHANS);
+    }
+
+    #[test]
+    fn invalid_heavier_branch_is_rejected() {
+        let (mut honest, key, _) = funded_chain(1);
+        let mut rival = fork_of(&honest, 1);
+        mine_block(&mut honest, "honest", Vec::new()).unwrap();
+
+        // Rakip dalda geçerli bir ödeme...
+        let payment = vec![TxOutput { amount: 50, address: String::from("bob"), script: None }];
+        let transaction = rival.utxos.build_transaction(&key, payment, 0).unwrap();
+        mine_block(&mut rival, "rival", vec![transaction.clone()]).unwrap();
+        // ...ve defter kontrolü atlanarak aynı çıktının ikinci kez harcandığı blok
+        let mut double_spend = rival.candidate_block("rival", Vec::new()).unwrap();
+        double_spend.transactions.push(transaction);
+        double_spend.merkle_root = double_spend.calculate_merkle_root();
+        mine(&mut double_spend);
+
+        let tip = honest.blocks.last().unwrap().hash;
+        honest.receive_block(rival.blocks[1].clone()).unwrap();
+        assert!(matches!(honest.receive_block(double_spend), Err(BlockError::Ledger(_))));
+        assert_eq!(honest.blocks.last().unwrap().hash, tip);
+        assert_eq!(honest.tree.side_block_count(), 1);
+    }
+
+    // Zincirin ucuna kazılmış bloğu ağ üzerinden gelmiş gibi alır
+    fn receive_next(blockchain: &mut Blockchain, miner: &str) {
+        let mut block = blockchain.candidate_block(miner, Vec::new()).unwrap();
+        mine(&mut block);
+        blockchain.receive_block(block).unwrap();
+    }
+
+    #[test]
+    fn side_block_off_its_branch_schedule_is_rejected() {
+        let mut honest = chain_with(3, "honest");
+        let rival = fork_of(&honest, 1);
+
+        // Takvimdeki zorluktan farklı bir zorlukla kazılmış yan blok
+        let mut wrong_bits = rival.candidate_block("rival", Vec::new()).unwrap();
+        wrong_bits.bits = difficulty::retarget(EASIEST_BITS, 1, 4);
+        mine(&mut wrong_bits);
+        assert!(matches!(honest.receive_block(wrong_bits), Err(BlockError::WrongDifficulty)));
+
+        // Ebeveyninden eski zaman damgalı yan blok
+        let mut too_old = rival.candidate_block("rival", Vec::new()).unwrap();
+        too_old.timestamp = rival.blocks[0].timestamp - 1;
+        mine(&mut too_old);
+        assert!(matches!(honest.receive_block(too_old), Err(BlockError::NonMonotonicTimestamp)));
+        assert_eq!(honest.tree.side_block_count(), 0);
+    }
+
+    #[test]
+    fn side_blocks_are_capped() {
+        let mut honest = chain_with(3, "honest");
+        let rival = fork_of(&honest, 1);
+        // Ana zincirden hafif, aynı yükseklikte birbirinden farklı yan bloklar
+        for i in 0..=MAX_SIDE_BLOCKS {
+            let mut block = rival.candidate_block(&format!("rival-{}", i), Vec::new()).unwrap();
+            mine(&mut block);
+            let events = honest.receive_block(block).unwrap();
+            assert!(matches!(events[..], [ChainEvent::SideBranch { .. }]));
+        }
+        assert_eq!(honest.tree.side_block_count(), MAX_SIDE_BLOCKS);
+    }
+
+    #[test]
+    fn buried_side_blocks_are_pruned() {
+        let mut honest = chain_with(2, "honest");
+        let mut rival = fork_of(&honest, 1);
+        mine_block(&mut rival, "rival", Vec::new()).unwrap();
+        honest.receive_block(rival.blocks[1].clone()).unwrap();
+
+        // Yan blok (yükseklik 1) ana zincirin ucundan MAX_SIDE_DEPTH blok aşağıya kadar tutulur
+        while honest.blocks.len() < 1 + MAX_SIDE_DEPTH {
+            receive_next(&mut honest, "honest");
+        }
+        assert_eq!(honest.tree.side_block_count(), 1);
+        receive_next(&mut honest, "honest");
+        assert_eq!(honest.tree.side_block_count(), 0);
+    }
+}
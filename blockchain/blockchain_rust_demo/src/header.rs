@@ -0,0 +1,187 @@
+// Blok başlığının sabit genişlikli ikili (binary) kodlaması
+// Hash, alanların metin hali yerine bu kanonik kodlama üzerinden hesaplanır; her alan sabit uzunlukta olduğu için
+// farklı alan değerleri asla aynı byte dizisini üretemez (ör. "1" + "23" ile "12" + "3")
+//
+// Düzen (toplam 84 byte, tamsayılar big-endian):
+//   sürüm (4) | önceki blok hash'i (32) | Merkle kökü (32) | zaman damgası (8) | bits (4) | nonce (4)
+
+use std::fmt::{self, Display, Formatter};
+
+use sha2::{Digest, Sha256};
+
+use crate::merkle::Hash;
+
+// Şu anki başlık sürümü
+pub const BLOCK_VERSION: u32 = 1;
+
+// Kodlanmış başlığın byte uzunluğu
+pub const HEADER_SIZE: usize = 4 + 32 + 32 + 8 + 4 + 4;
+
+// Genesis bloğun önceki hash'i (önceki blok yok)
+pub const ZERO_HASH: Hash = [0u8; 32];
+
+// Hash'e giren başlık alanları
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub version: u32,
+    pub previous_block_hash: Hash,
+    pub merkle_root: Hash,
+    pub timestamp: u64,
+    pub bits: u32,
+    pub nonce: u32,
+}
+
+// Kodlanmış başlık çözülemediğinde dönen hatalar
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    WrongLength { expected: usize, found: usize }, // Byte dizisi başlık uzunluğunda değil
+    UnsupportedVersion { version: u32 },           // Bilinmeyen başlık sürümü
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::WrongLength { expected, found } => {
+                write!(f, "header must be {} bytes, found {}", expected, found)
+            }
+            DecodeError::UnsupportedVersion { version } => write!(f, "unsupported header version {}", version),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl BlockHeader {
+    // Başlığı kanonik byte dizisine çevirir
+    pub fn encode(&self) -> [u8; HEADER_SIZE] {
+        let mut bytes = [0u8; HEADER_SIZE];
+        bytes[0..4].copy_from_slice(&self.version.to_be_bytes());
+        bytes[4..36].copy_from_slice(&self.previous_block_hash);
+        bytes[36..68].copy_from_slice(&self.merkle_root);
+        bytes[68..76].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes[76..80].copy_from_slice(&self.bits.to_be_bytes());
+        bytes[80..84].copy_from_slice(&self.nonce.to_be_bytes());
+        bytes
+    }
+
+    // Kanonik byte dizisinden başlığı geri üretir
+    pub fn decode(bytes: &[u8]) -> Result<BlockHeader, DecodeError> {
+        let bytes: &[u8; HEADER_SIZE] = bytes
+            .try_into()
+            .map_err(|_| DecodeError::WrongLength { expected: HEADER_SIZE, found: bytes.len() })?;
+        // Dilimler sabit aralıklardan alındığı için dönüşümler başarısız olamaz
+        let version = u32::from_be_bytes(bytes[0..4].try_into().unwrap());
+        if version != BLOCK_VERSION {
+            return Err(DecodeError::UnsupportedVersion { version });
+        }
+        Ok(BlockHeader {
+            version,
+            previous_block_hash: bytes[4..36].try_into().unwrap(),
+            merkle_root: bytes[36..68].try_into().unwrap(),
+            timestamp: u64::from_be_bytes(bytes[68..76].try_into().unwrap()),
+            bits: u32::from_be_bytes(bytes[76..80].try_into().unwrap()),
+            nonce: u32::from_be_bytes(bytes[80..84].try_into().unwrap()),
+        })
+    }
+
+    // Başlığın hash'i: kodlanmış başlığın çift SHA256'sı (Bitcoin'deki gibi)
+    pub fn hash(&self) -> Hash {
+        Sha256::digest(Sha256::digest(self.encode())).into()
+    }
+}
+
+// JSON'da hash'ler 32 sayılık dizi yerine hex string olarak yazılır
+pub mod hex_hash {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use crate::merkle::Hash;
+    use crate::{from_hex, to_hex};
+
+    pub fn serialize<S: Serializer>(hash: &Hash, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(hash))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Hash, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        from_hex(&hex)
+            .and_then(|bytes| Hash::try_from(bytes).ok())
+            .ok_or_else(|| de::Error::custom(format!("invalid 32-byte hex hash: {}", hex)))
+    }
+}
+
+// Hash listeleri için aynı hex gösterimi
+pub mod hex_hashes {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use crate::merkle::Hash;
+    use crate::{from_hex, to_hex};
+
+    pub fn serialize<S: Serializer>(hashes: &[Hash], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(hashes.iter().map(|hash| to_hex(hash)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Hash>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|hex| {
+                from_hex(&hex)
+                    .and_then(|bytes| Hash::try_from(bytes).ok())
+                    .ok_or_else(|| de::Error::custom(format!("invalid 32-byte hex hash: {}", hex)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header() -> BlockHeader {
+        BlockHeader {
+            version: BLOCK_VERSION,
+            previous_block_hash: [0xab; 32],
+            merkle_root: [0xcd; 32],
+            timestamp: 1_700_000_000,
+            bits: 0x2000ffff,
+            nonce: 42,
+        }
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        let header = header();
+        let bytes = header.encode();
+        assert_eq!(bytes.len(), HEADER_SIZE);
+        assert_eq!(&bytes[0..4], &[0, 0, 0, 1]);
+        assert_eq!(&bytes[80..84], &[0, 0, 0, 42]);
+        assert_eq!(BlockHeader::decode(&bytes), Ok(header));
+    }
+
+    #[test]
+    fn decode_rejects_wrong_length_and_version() {
+        let bytes = header().encode();
+        assert_eq!(
+            BlockHeader::decode(&bytes[..83]),
+            Err(DecodeError::WrongLength { expected: HEADER_SIZE, found: 83 })
+        );
+        let mut future = bytes;
+        future[3] = 2;
+        assert_eq!(BlockHeader::decode(&future), Err(DecodeError::UnsupportedVersion { version: 2 }));
+    }
+
+    #[test]
+    fn every_field_changes_the_hash() {
+        let base = header();
+        let mut variants = vec![base.clone(); 5];
+        variants[0].previous_block_hash[31] ^= 1;
+        variants[1].merkle_root[0] ^= 1;
+        variants[2].timestamp += 1;
+        variants[3].bits -= 1;
+        variants[4].nonce += 1;
+        for variant in variants {
+            assert_ne!(variant.hash(), base.hash());
+        }
+        // Çift SHA256: tek SHA256'dan farklı
+        assert_ne!(base.hash(), <[u8; 32]>::from(Sha256::digest(base.encode())));
+    }
+}
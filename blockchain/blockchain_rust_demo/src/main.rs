@@ -1,107 +1,334 @@
 // Rust ile basit bir blockchain simülasyonu
 // Konular: Blok oluşturma, hash hesaplama, PoW madenciliği, zincir doğrulama
 
-use std::time::{SystemTime, UNIX_EPOCH};
-use std::{fmt, str};
-use sha2::{Sha256, Digest};
-use std::thread;
+mod api;
+mod attack;
+mod clock;
+mod consensus;
+mod difficulty;
+mod export;
+mod explorer;
+mod fork;
+mod header;
+mod mempool;
+mod merkle;
+mod metrics;
+mod miner;
+mod node;
+mod script;
+mod storage;
+mod transaction;
+mod utxo;
+mod validation;
+mod wallet;
+
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use api::Api;
+use clock::{Clock, SimulatedClock, SystemClock, SIMULATED_EPOCH};
+use consensus::{BlockSignature, Consensus, ProofOfAuthority, ProofOfWork, SealError};
+use difficulty::{DEFAULT_BITS, TARGET_BLOCK_TIME};
+use explorer::ChainIndex;
+use export::ChainFormat;
+use fork::{BlockTree, ChainEvent};
+use header::{BlockHeader, BLOCK_VERSION, ZERO_HASH};
+use mempool::Mempool;
+use merkle::{Hash, MerkleProof};
+use metrics::MiningLog;
+use miner::{CancelToken, Miner, MiningEvent, MiningStats};
+use node::Node;
+use std::fmt::{self, Display, Formatter};
+use std::sync::{Arc, Mutex};
+use storage::ChainStore;
+use script::Script;
+use transaction::{OutPoint, Transaction, TxOutput};
+use utxo::{LedgerError, UtxoSet};
+use wallet::Keystore;
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// Zincirin saklandığı dosya (program yeniden başlatıldığında buradan yüklenir)
+const CHAIN_FILE: &str = "blockchain.jsonl";
 
-// Hash'in başlaması gereken zorluk hedefi
-const DIFFICULTY: usize = 0x1d00ffff;
+// Cüzdanların şifreli olarak saklandığı dosya
+const KEYSTORE_FILE: &str = "wallets.json";
 
-// Bir blok yapısı tanımlanıyor
+// Bir blok yapısı tanımlanıyor (diske JSON olarak yazılabilmesi için Serialize/Deserialize)
+// Hash'ler ham 32 byte olarak tutulur, JSON'da hex string olarak yazılır
+#[derive(Clone, Serialize, Deserialize)]
 struct Block {
-    index: u32,                      // Blok numarası (örneğin 0, 1, 2...)
-    previous_block_hash: String,    // Önceki bloğun hash değeri (zinciri kurmak için)
-    timestamp: u64,                 // Blok oluşturulma zamanı (Unix timestamp)
-    data: String,                   // Blok içinde saklanan veri (örnek: işlem bilgisi)
-    nonce: u32,                     // Doğru hash'i bulana kadar artan sayı (madencilikte kullanılır)
-    hash: String,                   // Bu bloğun hash sonucu (SHA256 ile)
+    index: u32,                        // Blok numarası (örneğin 0, 1, 2...)
+    version: u32,                      // Başlık kodlamasının sürümü
+    #[serde(with = "header::hex_hash")]
+    previous_block_hash: Hash,         // Önceki bloğun hash değeri (zinciri kurmak için)
+    timestamp: u64,                    // Blok oluşturulma zamanı (Unix timestamp)
+    #[serde(with = "header::hex_hash")]
+    merkle_root: Hash,                 // İşlem hash'lerinden hesaplanan Merkle kökü (başlığın parçası)
+    transactions: Vec<Transaction>,    // Blok içinde saklanan imzalı işlemler (gövde)
+    bits: u32,                         // Zorluk hedefi (Bitcoin'deki compact "bits" gösterimi)
+    nonce: u32,                        // Doğru hash'i bulana kadar artan sayı (madencilikte kullanılır)
+    #[serde(with = "header::hex_hash")]
+    hash: Hash,                        // Bu bloğun hash sonucu (başlığın çift SHA256'sı)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<BlockSignature>, // Yetki kanıtında bloğu mühürleyen yetkilinin imzası (iş kanıtında yok)
 }
 
 impl Block {
-    // Yeni bir blok oluşturur (henüz madencilik yapılmaz, hash boş)
-    fn new(index: u32, previous_block_hash: String, data: String) -> Block {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_secs();
+    // Yeni bir blok oluşturur (henüz madencilik yapılmaz, hash sıfır)
+    // Zaman damgası çağıran tarafından verilir (zincirin saati; testlerde ve tohumlu çalışmada sanal saat)
+    fn new(index: u32, previous_block_hash: Hash, transactions: Vec<Transaction>, bits: u32, timestamp: u64) -> Block {
+        let merkle_root = merkle::merkle_root(&transaction_hashes(&transactions));
         Block {
             index,
+            version: BLOCK_VERSION,
             previous_block_hash,
             timestamp,
-            data,
+            merkle_root,
+            transactions,
+            bits,
             nonce: 0,
-            hash: String::new(),
+            hash: ZERO_HASH,
+            signature: None,
         }
     }
 
-    // Blok içeriğini SHA256 ile hash'ler, nonce dahil edilir
-    fn calculate_hash(&self) -> String {
-        let mut hasher = Sha256::new();
-        // Hash girişine sırayla tüm veriler eklenir
-        hasher.update(self.index.to_string());
-        hasher.update(&self.previous_block_hash);
-        hasher.update(self.timestamp.to_string());
-        hasher.update(&self.data);
-        hasher.update(self.nonce.to_string());
-        // Hex formatında string olarak hash döndürülür
-        format!("{:x}", hasher.finalize())
+    // Hash'e giren başlık alanları (işlemler doğrudan değil Merkle kökü üzerinden katılır)
+    fn header(&self) -> BlockHeader {
+        BlockHeader {
+            version: self.version,
+            previous_block_hash: self.previous_block_hash,
+            merkle_root: self.merkle_root,
+            timestamp: self.timestamp,
+            bits: self.bits,
+            nonce: self.nonce,
+        }
     }
 
-    // Doğru hash değerini bulana kadar nonce artırarak madencilik yapar
-    fn mine(&mut self) {
-        loop {
-            self.hash = self.calculate_hash(); // Hash hesaplanır
-            if self.is_valid() {              // Eğer hedef zorluğu sağlıyorsa dur
-                break;
-            }
-            self.nonce += 1;                   // Aksi halde nonce'u artır, yeniden dene
-            thread::sleep(Duration::from_millis(10)); // Gerçekçi madencilik hissi için bekleme
-        }
+    // Kanonik ikili başlığı çift SHA256 ile hash'ler, nonce ve zorluk dahil edilir
+    fn calculate_hash(&self) -> Hash {
+        self.header().hash()
+    }
+
+    // İşlemlerden Merkle kökünü yeniden hesaplar (başlıktaki kökle karşılaştırmak için)
+    fn calculate_merkle_root(&self) -> Hash {
+        merkle::merkle_root(&transaction_hashes(&self.transactions))
     }
 
-    // Hash, belirlenen zorluk koşulunu sağlıyor mu kontrol edilir
-    fn is_valid(&self) -> bool {
-        let target = DIFFICULTY.to_string();
-        self.hash.starts_with(&target) // Gerçek sistemlerde bu daha karmaşıktır (örneğin 4 sıfırla başlama)
+    // tx_index sıradaki işlemin bu blokta olduğunu kanıtlayan Merkle yolunu üretir
+    fn prove(&self, tx_index: usize) -> Option<MerkleProof> {
+        merkle::prove(&transaction_hashes(&self.transactions), tx_index)
     }
 
     // Blok bilgileri ekrana yazdırılır
     fn display(&self) {
         println!("Block Index: {}", self.index);
-        println!("Previous Block Hash: {}", self.previous_block_hash);
+        println!("Version: {}", self.version);
+        println!("Previous Block Hash: {}", to_hex(&self.previous_block_hash));
         println!("Timestamp: {}", self.timestamp);
-        println!("Data: {}", self.data);
+        println!("Merkle Root: {}", to_hex(&self.merkle_root));
+        println!("Transactions: {}", self.transactions.len());
+        for transaction in &self.transactions {
+            if transaction.is_coinbase() {
+                println!("  Tx {} (coinbase)", transaction.txid());
+            } else {
+                println!("  Tx {}", transaction.txid());
+            }
+            for input in &transaction.inputs {
+                println!("    in  {}:{}", input.previous_output.txid, input.previous_output.index);
+                if let Some(unlocking) = &input.unlocking {
+                    println!("        unlocking: {}", unlocking);
+                }
+            }
+            for output in &transaction.outputs {
+                println!("    out {} -> {}", output.amount, output.address);
+                if let Some(script) = &output.script {
+                    println!("        locking: {}", script);
+                }
+            }
+        }
+        println!("Bits: {:#010x}", self.bits);
         println!("Nonce: {}", self.nonce);
-        println!("Hash: {}", self.hash);
+        println!("Hash: {}", to_hex(&self.hash));
+        if let Some(seal) = &self.signature {
+            println!("Sealed by: {}", seal.public_key);
+        }
+    }
+}
+
+// İşlemlerin kimliklerini (Merkle ağacının yaprakları) sırasıyla döndürür
+fn transaction_hashes(transactions: &[Transaction]) -> Vec<Hash> {
+    transactions.iter().map(Transaction::hash).collect()
+}
+
+// Kazılmış bir blok zincire eklenemediğinde dönen hatalar
+#[derive(Debug)]
+enum BlockError {
+    Duplicate,              // Blok daha önce alınmış
+    NotOnTip,               // Blok zincirin ucundaki bloğun üzerine kurulmamış
+    InvalidHash,            // Saklanan hash başlıkla uyuşmuyor
+    InvalidSeal(SealError), // Mühür uzlaşma kurallarına uymuyor (hedef sağlanmıyor ya da imza geçersiz)
+    MerkleRootMismatch,     // Başlıktaki Merkle kökü işlemlerle uyuşmuyor
+    WrongDifficulty,        // Blok ayarlama takvimindeki zorlukla kazılmamış
+    NonMonotonicTimestamp,  // Zaman damgası önceki bloğunkinden küçük
+    TooManySideBlocks,      // Yan dal sınırı dolu ve yer açmak için atılabilecek başka dal yok
+    Ledger(LedgerError),    // İşlemler deftere uygulanamadı
+}
+
+impl Display for BlockError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockError::Duplicate => write!(f, "block is already known"),
+            BlockError::NotOnTip => write!(f, "block does not extend the current tip"),
+            BlockError::InvalidHash => write!(f, "block hash does not match its header"),
+            BlockError::InvalidSeal(err) => write!(f, "invalid seal: {}", err),
+            BlockError::MerkleRootMismatch => write!(f, "merkle root does not match the transactions"),
+            BlockError::WrongDifficulty => write!(f, "block difficulty does not match the retarget schedule"),
+            BlockError::NonMonotonicTimestamp => write!(f, "block timestamp is earlier than its parent's"),
+            BlockError::TooManySideBlocks => write!(f, "too many side-branch blocks are already stored"),
+            BlockError::Ledger(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for BlockError {}
+
+impl From<LedgerError> for BlockError {
+    fn from(err: LedgerError) -> Self {
+        BlockError::Ledger(err)
+    }
+}
+
+impl From<SealError> for BlockError {
+    fn from(err: SealError) -> Self {
+        BlockError::InvalidSeal(err)
     }
 }
 
 // Blockchain yapısı: Bloklardan oluşan bir vektör
-struct Blockchain {
-    blocks: Vec<Block>, // Blokları tutan dizi
+// Uzlaşma motoru tür parametresidir; program başlarken komut satırına göre bir kez seçilir
+struct Blockchain<C: Consensus = ProofOfWork> {
+    blocks: Vec<Block>,            // Blokları tutan dizi
+    bits: u32,                     // Genesis bloğun zorluğu (sonraki bloklar ayarlama takvimine göre belirlenir)
+    utxos: UtxoSet,                // Zincirin ucundaki harcanmamış çıktılar (her blokla güncellenir)
+    miner: Miner,                  // Yeni blokları kazan madenci ayarları (iş parçacığı sayısı, yavaşlatma)
+    consensus: Arc<C>,             // Blokları mühürleyip doğrulayan uzlaşma motoru (kopyalar ve madenci iş parçacıkları paylaşır)
+    clock: Arc<dyn Clock>,         // Zaman damgalarının okunduğu saat (varsayılan sistem saati)
+    tree: BlockTree,               // Ana zincir dışında kalan yan dallar ve yetim bloklar
+    index: ChainIndex,             // Ana zincir üzerindeki sorgular için indeksler (hash, adres)
+    mempool: Mempool,              // Bloğa girmeyi bekleyen işlemler
+    mining_log: MiningLog,         // Bu oturumda kazılan blokların süresi ve deneme sayısı (istatistikler için)
 }
 
-impl Blockchain {
-    // Boş bir blockchain başlatılır (henüz genesis block yok)
-    fn new() -> Blockchain {
-        Blockchain { blocks: Vec::new() }
+// Kopyalanabilir: atölye senaryoları zincirin bir kopyası üzerinde çalışır
+// (derive, motorun da Clone olmasını şart koşacağı için elle yazılır; kopya aynı motoru paylaşır)
+impl<C: Consensus> Clone for Blockchain<C> {
+    fn clone(&self) -> Self {
+        Blockchain {
+            blocks: self.blocks.clone(),
+            bits: self.bits,
+            utxos: self.utxos.clone(),
+            miner: self.miner.clone(),
+            consensus: Arc::clone(&self.consensus),
+            clock: Arc::clone(&self.clock),
+            tree: self.tree.clone(),
+            index: self.index.clone(),
+            mempool: self.mempool.clone(),
+            mining_log: self.mining_log.clone(),
+        }
+    }
+}
+
+impl<C: Consensus> Blockchain<C> {
+    // Verilen uzlaşma motoru ve başlangıç zorluğu ile boş blockchain oluşturur
+    // (Yetki kanıtında zorluk kullanılmaz)
+    fn with_consensus(consensus: Arc<C>, bits: u32) -> Blockchain<C> {
+        Blockchain {
+            blocks: Vec::new(),
+            bits,
+            utxos: UtxoSet::default(),
+            miner: Miner::default(),
+            consensus,
+            clock: Arc::new(SystemClock),
+            tree: BlockTree::default(),
+            index: ChainIndex::default(),
+            mempool: Mempool::default(),
+            mining_log: MiningLog::default(),
+        }
     }
 
-    // Zincire yeni bir blok ekler (veri girilir, önceki hash'e bağlanır, madencilik yapılır)
-    fn add_block(&mut self, data: String) {
+    // Zincirin ucuna eklenecek yeni bir blok hazırlar (işlemler deftere göre kontrol edilir, önceki hash'e bağlanır)
+    // Madenci blok ödülünü ve işlem ücretlerini ilk işlem (coinbase) ile kendi adresine yazar
+    // Blok henüz kazılmamıştır; madencilikten sonra add_block ile zincire eklenir
+    fn candidate_block(&self, miner: &str, transactions: Vec<Transaction>) -> Result<Block, LedgerError> {
         let index = self.blocks.len() as u32; // Yeni bloğun indeksi
         let previous_block_hash = if index == 0 {
-            String::from("0") // İlk bloksa (genesis block), önceki hash "0" kabul edilir
+            ZERO_HASH // İlk bloksa (genesis block), önceki hash sıfır kabul edilir
         } else {
-            self.blocks[index as usize - 1].hash.clone() // Önceki bloğun hash değeri alınır
+            self.blocks[index as usize - 1].hash // Önceki bloğun hash değeri alınır
         };
-        let mut block = Block::new(index, previous_block_hash, data); // Blok oluşturulur
-        block.mine(); // Madencilik yapılır (hash bulunana kadar nonce denenir)
+        // Geçersiz işlem varsa madenciliğe hiç başlanmaz
+        let fees = self.utxos.clone().apply_transactions(&transactions)?;
+        let coinbase = Transaction::coinbase(miner.to_string(), utxo::BLOCK_REWARD + fees, index as u64);
+        let transactions: Vec<Transaction> = std::iter::once(coinbase).chain(transactions).collect();
+        let bits = self.consensus.expected_bits(self.bits, &self.blocks); // Uzlaşma kurallarına göre bu yüksekliğin zorluğu
+        let timestamp = self.clock.now();
+        let mut block = Block::new(index, previous_block_hash, transactions, bits, timestamp); // Blok oluşturulur
+        // Saati geride kalan bir düğüm, önceki bloktan daha eski zaman damgası yazmamalı
+        if let Some(previous_block) = self.blocks.last() {
+            block.timestamp = block.timestamp.max(previous_block.timestamp);
+        }
+        Ok(block)
+    }
+
+    // Kazılmış bir bloğu zincirin ucuna ekler
+    // Blok ucun üzerine kurulmalı, geçerli bir hash'e, mühre ve takvimdeki zorluğa sahip olmalı, işlemleri deftere uymalı
+    fn add_block(&mut self, block: Block) -> Result<(), BlockError> {
+        let tip_hash = self.blocks.last().map_or(ZERO_HASH, |tip| tip.hash);
+        if block.index as usize != self.blocks.len() || block.previous_block_hash != tip_hash {
+            return Err(BlockError::NotOnTip);
+        }
+        if block.hash != block.calculate_hash() {
+            return Err(BlockError::InvalidHash);
+        }
+        self.consensus.verify_seal(&block)?;
+        if block.merkle_root != block.calculate_merkle_root() {
+            return Err(BlockError::MerkleRootMismatch);
+        }
+        if block.bits != self.consensus.expected_bits(self.bits, &self.blocks) {
+            return Err(BlockError::WrongDifficulty);
+        }
+        if self.blocks.last().is_some_and(|tip| block.timestamp < tip.timestamp) {
+            return Err(BlockError::NonMonotonicTimestamp);
+        }
+        self.utxos.apply_block(&block.transactions, block.index as u64)?; // Hata olursa defter değişmez
+        self.index.insert(self.blocks.len(), &block); // Sorgu indeksleri güncellenir
         self.blocks.push(block); // Zincire eklenir
+        self.clock.advance(); // Sanal saat bir blok ilerler
+        self.mempool.revalidate(&self.utxos); // Bloğa giren ya da artık çakışan işlemler havuzdan çıkar
+        Ok(())
+    }
+
+    // Aday bloğu zincirin uzlaşma motoruyla mühürler (iş kanıtında kazar, yetki kanıtında imzalar)
+    fn seal(&self, block: &mut Block, cancel: &CancelToken) -> Result<MiningStats, SealError> {
+        self.consensus.seal(block, &self.miner, cancel, &mut |_| {})
+    }
+
+    // Bütün blokları baştan oynatarak UTXO kümesini yeniden kurar
+    // (Çifte harcama, fazla harcama, sahte imza gibi hatalarda ilk hatayı döndürür)
+    fn replay_ledger(&self) -> Result<UtxoSet, LedgerError> {
+        let mut utxos = UtxoSet::default();
+        for (height, block) in self.blocks.iter().enumerate() {
+            utxos.apply_block(&block.transactions, height as u64)?;
+        }
+        Ok(utxos)
+    }
+
+    // Adresin zincirin ucundaki bakiyesi
+    fn balance_of(&self, address: &str) -> u64 {
+        self.utxos.balance_of(address)
     }
 
     // Tüm bloklar terminalde gösterilir
@@ -110,72 +337,983 @@ impl Blockchain {
             block.display();
             println!("-------------------------");
         }
+        println!("Main chain: {} block(s), total work {}", self.blocks.len(), self.total_work());
+        println!(
+            "Side branches: {} block(s), orphans: {}",
+            self.tree.side_block_count(),
+            self.tree.orphan_count()
+        );
+        println!("Mempool: {} transaction(s), {} byte(s)", self.mempool.len(), self.mempool.total_size());
+    }
+}
+
+// Byte dizisini küçük harfli hex string'e çevirir
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Hex string'i byte dizisine çevirir (geçersizse None)
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+// Bloğu zincire işler, değişiklikleri diske yazar ve ana zincirin ucu değiştiyse eşlere duyurur
+// Blok bu düğümde kazıldıysa madencilik istatistikleri de kaydedilir
+// (Menü ve API madenciliği ile içe aktarma aynı yolu kullanır)
+// Duyuru eşlere TCP ile yazıldığı için zincirin kilidi bırakıldıktan sonra yapılır
+fn publish_block<C: Consensus>(
+    chain: &Mutex<Blockchain<C>>,
+    store: &ChainStore,
+    node: Option<&Node<C>>,
+    block: Block,
+    stats: Option<MiningStats>,
+) -> Result<Vec<ChainEvent>, BlockError> {
+    let hash = block.hash;
+    let (events, tip) = {
+        let mut blockchain = chain.lock().unwrap();
+        let events = blockchain.receive_block(block)?;
+        if let Some(stats) = stats {
+            blockchain.record_mining(hash, stats);
+        }
+        // Uca eklenen blok dosyaya eklenir; zincir yeniden düzenlendiyse dosya baştan yazılır
+        if let Err(err) = store.save(&blockchain, &events) {
+            eprintln!("Failed to save block: {}", err);
+        }
+        let tip = blockchain.blocks.last().map(|tip| tip.hash);
+        (events, tip)
+    };
+    let changed = events
+        .iter()
+        .any(|event| matches!(event, ChainEvent::Extended { .. } | ChainEvent::Reorganized { .. }));
+    if let (Some(node), Some(tip), true) = (node, tip, changed) {
+        node.announce_block(&tip);
+    }
+    Ok(events)
+}
+
+// Ödemeleri kullanıcıdan alır, tek bir imzalı işlemde toplayıp bekleyen işlem havuzuna ekler
+// Düğüm modunda işlem eşlere de gönderilir
+fn send_payments<C: Consensus>(chain: &Mutex<Blockchain<C>>, node: Option<&Node<C>>, key: &SigningKey) {
+    println!("Enter payments as '<recipient> <amount>' (empty line to finish)");
+    let mut payments = Vec::new();
+    loop {
+        let line = prompt("> ");
+        if line.is_empty() {
+            break;
+        }
+        match line.split_once(' ').map(|(to, amount)| (to, amount.trim().parse::<u64>())) {
+            // Sağlama toplamı tutmayan adres büyük olasılıkla yanlış yazılmıştır; betik adresine de betiksiz ödenemez
+            // Her iki durumda para kaybolmasın diye ödeme reddedilir
+            Some((recipient, Ok(amount))) => match wallet::check_payment_address(recipient) {
+                Ok(()) => payments.push(TxOutput { amount, address: recipient.to_string(), script: None }),
+                Err(err) => println!("Cannot pay {}: {}", recipient, err),
+            },
+            _ => println!("Invalid payment, expected '<recipient> <amount>'"),
+        }
+    }
+    if payments.is_empty() {
+        println!("No payments entered.");
+        return;
+    }
+    // Ücret boş bırakılırsa sıfır kabul edilir; yüksek ücretli işlemler bloğa önce girer
+    let fee = prompt("Fee (empty for 0): ").parse::<u64>().unwrap_or(0);
+
+    // Sonuç önce yerel değişkene alınır; böylece eşlere gönderim sırasında zincirin kilidi tutulmaz
+    let result = chain.lock().unwrap().send(key, payments, fee);
+    match result {
+        Ok((transaction, fee)) => {
+            println!("Transaction {} added to the mempool (fee {})", transaction.txid(), fee);
+            if let Some(node) = node {
+                node.broadcast_transaction(&transaction);
+            }
+        }
+        Err(err) => println!("Transaction rejected: {}", err),
+    }
+}
+
+// Betik katılımcısının imza anahtarı: "me" oturumun anahtarıdır, diğer adlar keystore'daki cüzdanlardır (parola sorulur)
+fn participant_key(keystore: &Keystore, key: &SigningKey, name: &str) -> Result<SigningKey, String> {
+    if name == "me" {
+        return Ok(key.clone());
     }
+    let passphrase = prompt(&format!("Passphrase for {}: ", name));
+    keystore.unlock(name, &passphrase).map(|wallet| wallet.key).map_err(|err| format!("{}: {}", name, err))
+}
 
-    // Zincirin geçerliliği kontrol edilir (tüm bloklar doğru hash'e ve bağlantıya sahip mi?)
-    fn is_chain_valid(&self) -> bool {
-        for i in 1..self.blocks.len() {
-            let current_block = &self.blocks[i];
-            let previous_block = &self.blocks[i - 1];
+// Kilitleme betiğindeki açık anahtar: hex olarak ya da katılımcı adıyla verilebilir
+fn participant_public_key(keystore: &Keystore, key: &SigningKey, word: &str) -> Result<VerifyingKey, String> {
+    let hex_key = from_hex(word)
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok());
+    match hex_key {
+        Some(public_key) => Ok(public_key),
+        None => participant_key(keystore, key, word).map(|key| key.verifying_key()),
+    }
+}
 
-            // Önceki hash doğru mu?
-            if current_block.previous_block_hash != previous_block.hash {
-                return false;
+// Oturumun anahtarıyla betikli bir çıktıya ödeme yapar: çoklu imza, zaman kilidi ya da elle yazılmış betik
+// Çıktı betiğin adresine yazılır; harcamak için betiği açan imzalar gerekir (menü 18)
+fn lock_in_script<C: Consensus>(
+    chain: &Mutex<Blockchain<C>>,
+    node: Option<&Node<C>>,
+    keystore: &Keystore,
+    key: &SigningKey,
+) {
+    println!("Participants are 'me', a wallet name or a public key in hex");
+    let line = prompt("Script (multisig <m> <participant>... | timelock <height> <participant> | asm <script>): ");
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let script = match words.as_slice() {
+        ["multisig", required, participants @ ..] if !participants.is_empty() => {
+            let Ok(required) = required.parse::<usize>() else {
+                println!("Invalid signature count.");
+                return;
+            };
+            if required == 0 || required > participants.len() || participants.len() > script::MAX_MULTISIG_KEYS {
+                println!("Need 1 <= m <= n <= {} for an m-of-n multisig.", script::MAX_MULTISIG_KEYS);
+                return;
             }
+            let keys: Result<Vec<VerifyingKey>, String> =
+                participants.iter().map(|word| participant_public_key(keystore, key, word)).collect();
+            keys.map(|keys| Script::multisig(required, &keys))
+        }
+        ["timelock", height, participant] => match height.parse::<u64>() {
+            Ok(height) => participant_public_key(keystore, key, participant).map(|key| Script::timelock(height, &key)),
+            Err(_) => Err(String::from("invalid height")),
+        },
+        ["asm", ..] => line.trim_start()["asm".len()..].parse::<Script>().map_err(|err| err.to_string()),
+        _ => Err(String::from("unknown script form")),
+    };
+    let script = match script {
+        Ok(script) => script,
+        Err(err) => {
+            println!("Invalid script: {}", err);
+            return;
+        }
+    };
+    let address = wallet::script_address(&script);
+    println!("Locking script: {}", script);
+    println!("Script address: {}", address);
 
-            // Hash yeniden hesaplandığında aynı mı?
-            if current_block.hash != current_block.calculate_hash() {
-                return false;
+    let Ok(amount) = prompt("Amount: ").parse::<u64>() else {
+        println!("Invalid amount.");
+        return;
+    };
+    let fee = prompt("Fee (empty for 0): ").parse::<u64>().unwrap_or(0);
+    let payment = vec![TxOutput { amount, address, script: Some(script) }];
+    let result = chain.lock().unwrap().send(key, payment, fee);
+    match result {
+        Ok((transaction, fee)) => {
+            println!("Transaction {} added to the mempool (fee {})", transaction.txid(), fee);
+            println!("Locked output: {}:0", transaction.txid());
+            if let Some(node) = node {
+                node.broadcast_transaction(&transaction);
             }
         }
-        true
+        Err(err) => println!("Transaction rejected: {}", err),
     }
 }
 
+// Betikli bir çıktıyı harcar: imzalayanlar sırayla seçilir, betik doğrulaması havuza eklenirken yapılır
+fn spend_from_script<C: Consensus>(
+    chain: &Mutex<Blockchain<C>>,
+    node: Option<&Node<C>>,
+    keystore: &Keystore,
+    key: &SigningKey,
+) {
+    let outpoint = prompt("Locked output (<txid>:<index>): ");
+    let outpoint = outpoint
+        .split_once(':')
+        .and_then(|(txid, index)| Some(OutPoint { txid: txid.to_string(), index: index.parse().ok()? }));
+    let Some(outpoint) = outpoint else {
+        println!("Invalid output, expected '<txid>:<index>'");
+        return;
+    };
+    let output = chain.lock().unwrap().utxos.output(&outpoint).cloned();
+    let Some(TxOutput { amount, script: Some(script), .. }) = output else {
+        println!("No unspent scripted output {}:{} on the main chain.", outpoint.txid, outpoint.index);
+        return;
+    };
+    println!("Locking script: {}", script);
+    println!("Amount: {}", amount);
+
+    // Çoklu imzada imzalayanlar kilitleme betiğindeki anahtarlarla aynı sırada verilmelidir
+    let signers = prompt("Signers in script order ('me' or wallet names, space separated): ");
+    let keys: Result<Vec<SigningKey>, String> =
+        signers.split_whitespace().map(|name| participant_key(keystore, key, name)).collect();
+    let keys = match keys {
+        Ok(keys) => keys,
+        Err(err) => {
+            println!("Failed to unlock signer {}", err);
+            return;
+        }
+    };
+    let recipient = prompt("Recipient address: ");
+    if let Err(err) = wallet::check_payment_address(&recipient) {
+        println!("Cannot pay {}: {}", recipient, err);
+        return;
+    }
+    let fee = prompt("Fee (empty for 0): ").parse::<u64>().unwrap_or(0);
+    let Some(remaining) = amount.checked_sub(fee) else {
+        println!("Fee exceeds the locked amount.");
+        return;
+    };
+    let payment = vec![TxOutput { amount: remaining, address: recipient, script: None }];
+    let transaction = Transaction::new_script_spend(&keys, vec![outpoint], payment);
+    let result = chain.lock().unwrap().submit_transaction(transaction.clone());
+    match result {
+        Ok(fee) => {
+            println!("Transaction {} added to the mempool (fee {})", transaction.txid(), fee);
+            if let Some(node) = node {
+                node.broadcast_transaction(&transaction);
+            }
+        }
+        Err(err) => println!("Transaction rejected: {}", err),
+    }
+}
+
+// Zincir gezgini: sorguya göre blokları ya da adresin işlemlerini sayfa sayfa tablo halinde gösterir
+// Zincirin kilidi sayfa başına alınır; kullanıcı sayfalar arasında gezinirken ağ ve madencilik beklemez
+fn explore<C: Consensus>(chain: &Mutex<Blockchain<C>>) {
+    let query = prompt("Query (empty for all blocks | height <n> | hash <hex> | address <addr> | time <from> <to>): ");
+    let words: Vec<&str> = query.split_whitespace().collect();
+    match words.as_slice() {
+        [] => {
+            // En yeni bloklar önce gösterilir
+            let total = chain.lock().unwrap().blocks.len();
+            page_through(explorer::BLOCK_TABLE_HEADER, total, |rows| {
+                let blockchain = chain.lock().unwrap();
+                rows.filter_map(|row| blockchain.blocks.len().checked_sub(row + 1))
+                    .filter_map(|height| blockchain.block_at(height))
+                    .map(explorer::block_row)
+                    .collect()
+            });
+        }
+        ["height", height] => match height.parse().ok().and_then(|height| chain.lock().unwrap().block_at(height).cloned()) {
+            Some(block) => block.display(),
+            None => println!("No block at that height."),
+        },
+        ["hash", hash] => {
+            let hash = from_hex(hash).and_then(|bytes| Hash::try_from(bytes).ok());
+            match hash.and_then(|hash| chain.lock().unwrap().block_by_hash(&hash).cloned()) {
+                Some(block) => block.display(),
+                None => println!("No block with that hash on the main chain."),
+            }
+        }
+        ["address", address] => {
+            let total = chain.lock().unwrap().transactions_of(address).len();
+            println!("{} transaction(s) involving {}", total, address);
+            page_through(explorer::TRANSACTION_TABLE_HEADER, total, |rows| {
+                let blockchain = chain.lock().unwrap();
+                let transactions = blockchain.transactions_of(address);
+                rows.filter_map(|row| transactions.get(row))
+                    .map(|(location, transaction)| explorer::transaction_row(*location, transaction, address))
+                    .collect()
+            });
+        }
+        ["time", from, to] => {
+            // Sınırlar Unix zaman damgası ya da YYYY-MM-DD tarihi olabilir (bitiş tarihi gün sonuna kadar dahil)
+            let (Some(from), Some(to)) = (parse_time(from, false), parse_time(to, true)) else {
+                println!("Invalid time, expected a Unix timestamp or YYYY-MM-DD.");
+                return;
+            };
+            let (start, total) = {
+                let blockchain = chain.lock().unwrap();
+                let blocks = blockchain.blocks_between(from, to);
+                (blocks.first().map_or(0, |block| block.index as usize), blocks.len())
+            };
+            println!("{} block(s) between {} and {}", total, explorer::format_time(from), explorer::format_time(to));
+            page_through(explorer::BLOCK_TABLE_HEADER, total, |rows| {
+                let blockchain = chain.lock().unwrap();
+                rows.filter_map(|row| blockchain.block_at(start + row)).map(explorer::block_row).collect()
+            });
+        }
+        _ => println!("Unknown query."),
+    }
+}
+
+// Satırları PAGE_SIZE'lık sayfalar halinde gösterir; render istenen satır aralığını üretir
+fn page_through(header: &str, total: usize, render: impl Fn(std::ops::Range<usize>) -> Vec<String>) {
+    if total == 0 {
+        println!("Nothing to show.");
+        return;
+    }
+    let pages = total.div_ceil(explorer::PAGE_SIZE);
+    let mut page = 0;
+    loop {
+        println!("{}", header);
+        let start = page * explorer::PAGE_SIZE;
+        for row in render(start..(start + explorer::PAGE_SIZE).min(total)) {
+            println!("{}", row);
+        }
+        let answer = prompt(&format!("Page {}/{} - [n]ext, [p]revious, page number, empty to quit: ", page + 1, pages));
+        page = match answer.as_str() {
+            "" => break,
+            "n" => (page + 1).min(pages - 1),
+            "p" => page.saturating_sub(1),
+            number => match number.parse::<usize>() {
+                Ok(number) if (1..=pages).contains(&number) => number - 1,
+                _ => page,
+            },
+        };
+    }
+}
+
+// Unix zaman damgasını ya da YYYY-MM-DD tarihini çözer; end ise tarih gün sonuna yuvarlanır
+fn parse_time(input: &str, end: bool) -> Option<u64> {
+    if let Ok(timestamp) = input.parse() {
+        return Some(timestamp);
+    }
+    let date = chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d").ok()?;
+    let time = if end { date.and_hms_opt(23, 59, 59)? } else { date.and_hms_opt(0, 0, 0)? };
+    u64::try_from(time.and_utc().timestamp()).ok()
+}
+
+// Kullanıcıya mesaj gösterip bir satır okur
+fn prompt(message: &str) -> String {
+    print!("{}", message);
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Failed to read input");
+    input.trim().to_string()
+}
+
+// Yetki kanıtı motorunu keystore'daki cüzdanlardan kurar: adlar imza sırasıyla verilir
+// Her yetkilinin parolası sorulur; boş bırakılan yetkilinin anahtarı bu düğüme yüklenmez (sırası gelince başka düğüm imzalar)
+fn authority_consensus(keystore: &Keystore, names: &[&str]) -> Result<ProofOfAuthority, String> {
+    let mut authorities = Vec::new();
+    let mut keys = Vec::new();
+    for name in names {
+        let Some(entry) = keystore.wallets().iter().find(|entry| entry.name == *name) else {
+            return Err(format!("no wallet named {} in {}", name, keystore.path().display()));
+        };
+        authorities.push(entry.address.clone());
+        let passphrase = prompt(&format!("Passphrase for authority {} (empty to skip): ", name));
+        if passphrase.is_empty() {
+            continue;
+        }
+        let wallet = keystore.unlock(name, &passphrase).map_err(|err| format!("{}: {}", name, err))?;
+        keys.push(wallet.key);
+    }
+    Ok(ProofOfAuthority::new(authorities, keys))
+}
+
+// Başlangıç zorluğu komut satırından hex olarak verilebilir (örnek: cargo run -- 0x207fffff)
+fn parse_bits(arg: &str) -> Option<u32> {
+    u32::from_str_radix(arg.trim_start_matches("0x"), 16).ok()
+}
+
 // Uygulamanın ana fonksiyonu - kullanıcı arayüzü gibi çalışır
 fn main() {
     println!("=== Blockchain Simulator ===");
-    let mut blockchain = Blockchain::new(); // Yeni blockchain başlatılır
+    // Zincir diskten yüklenir; dosya yoksa yeni blockchain başlatılır
+    // (zorluk argüman olarak verildiyse yeni zincir o zorlukla başlar)
+    // İkinci argüman, her hash denemesinden sonra beklenecek milisaniye (örnek: cargo run -- 0x2000ffff 10)
+    // Üçüncü argüman, bir bloğa alınacak işlemlerin toplam boyut sınırı (byte)
+    // Düğüm modunda ilk argüman "node", ardından dinlenecek port ve bağlanılacak eşler gelir
+    // (örnek: cargo run -- node 9001 127.0.0.1:9000); her düğüm zincirini kendi dosyasında tutar
+    // "--api <port>" her iki modda da zinciri HTTP üzerinden JSON API ile açar (örnek: cargo run -- --api 8080)
+    // "--poa <cüzdan,...>" iş kanıtı yerine keystore'daki cüzdanların sırayla imzaladığı yetki kanıtını kullanır
+    // (örnek: cargo run -- --poa alice,bob); yetki kanıtı zinciri ayrı bir dosyada tutulur
+    // "--seed <sayı>" tohumlu çalışma: oturum anahtarı ve saldırı senaryosu tohumdan türetilir, saat sanaldır ve
+    // madencilik tek iş parçacığıyla yapılır; aynı tohum ve aynı girdi dizisi byte byte aynı zincir dosyasını üretir
+    // (örnek: cargo run -- --seed 42 0x207fffff < script.txt); zincir tohuma özel bir dosyada tutulur
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let api_port = match args.iter().position(|arg| arg == "--api") {
+        Some(i) => {
+            let port = args.get(i + 1).and_then(|port| port.parse::<u16>().ok());
+            let Some(port) = port else {
+                eprintln!("Usage: --api <port>");
+                std::process::exit(2);
+            };
+            args.drain(i..i + 2);
+            Some(port)
+        }
+        None => None,
+    };
+    let authorities = match args.iter().position(|arg| arg == "--poa") {
+        Some(i) => {
+            let Some(names) = args.get(i + 1).cloned() else {
+                eprintln!("Usage: --poa <wallet name>[,<wallet name>...]");
+                std::process::exit(2);
+            };
+            args.drain(i..i + 2);
+            Some(names)
+        }
+        None => None,
+    };
+    let seed = match args.iter().position(|arg| arg == "--seed") {
+        Some(i) => {
+            let Some(seed) = args.get(i + 1).and_then(|seed| seed.parse::<u64>().ok()) else {
+                eprintln!("Usage: --seed <number>");
+                std::process::exit(2);
+            };
+            args.drain(i..i + 2);
+            Some(seed)
+        }
+        None => None,
+    };
+    let (node_port, peers, args) = match args.split_first() {
+        Some((mode, rest)) if mode == "node" => {
+            let Some(port) = rest.first().and_then(|port| port.parse::<u16>().ok()) else {
+                eprintln!("Usage: blockchain_rust node <port> [peer address...]");
+                std::process::exit(2);
+            };
+            (Some(port), rest[1..].to_vec(), &[][..])
+        }
+        _ => (None, Vec::new(), &args[..]),
+    };
+    // Yetki kanıtında zorluk yoktur; bloklar bits = 0 taşır
+    let bits = match authorities {
+        Some(_) => 0,
+        None => args.first().map(String::as_str).and_then(parse_bits).unwrap_or(DEFAULT_BITS),
+    };
+    let throttle = args.get(1).and_then(|ms| ms.parse().ok()).map(Duration::from_millis);
+    let max_block_size = args.get(2).and_then(|size| size.parse().ok());
+
+    let keystore = match Keystore::open(KEYSTORE_FILE) {
+        Ok(keystore) => keystore,
+        Err(err) => {
+            // Bozuk keystore'un üzerine yeni cüzdan yazıp anahtarları kaybetmemek için program durdurulur
+            eprintln!("Failed to open {}: {}", KEYSTORE_FILE, err);
+            std::process::exit(1);
+        }
+    };
+    println!("{} wallet(s) in {}", keystore.wallets().len(), keystore.path().display());
+
+    let mut suffix = String::new();
+    if authorities.is_some() {
+        suffix.push_str("-poa");
+    }
+    if let Some(seed) = seed {
+        suffix.push_str(&format!("-seed{}", seed));
+    }
+    let store = match node_port {
+        Some(port) => ChainStore::new(format!("blockchain-{}{}.jsonl", port, suffix)),
+        None => ChainStore::new(CHAIN_FILE.replace(".jsonl", &format!("{}.jsonl", suffix))),
+    };
+
+    let options = Options { api_port, node_port, peers, seed, bits, throttle, max_block_size };
+    // Uzlaşma motoru burada bir kez seçilir; programın geri kalanı seçilen motorun türüyle çalışır
+    match &authorities {
+        Some(names) => match authority_consensus(&keystore, &names.split(',').collect::<Vec<_>>()) {
+            Ok(consensus) => run(consensus, keystore, store, options),
+            Err(err) => {
+                eprintln!("Failed to set up proof-of-authority: {}", err);
+                std::process::exit(2);
+            }
+        },
+        None => run(ProofOfWork, keystore, store, options),
+    }
+}
+
+// Komut satırından okunan, uzlaşma motorundan bağımsız ayarlar
+struct Options {
+    api_port: Option<u16>,          // JSON API'nin dinleyeceği port
+    node_port: Option<u16>,         // Düğüm modunda dinlenecek port
+    peers: Vec<String>,             // Düğüm modunda bağlanılacak eşler
+    seed: Option<u64>,              // Tohumlu çalışmanın tohumu
+    bits: u32,                      // Yeni zincirin başlangıç zorluğu
+    throttle: Option<Duration>,     // Her hash denemesinden sonra beklenecek süre
+    max_block_size: Option<usize>,  // Bir bloğa alınacak işlemlerin toplam boyut sınırı
+}
+
+// Zinciri seçilen uzlaşma motoruyla yükler, düğümü ve API'yi başlatır ve menüyü çalıştırır
+fn run<C: Consensus>(consensus: C, mut keystore: Keystore, store: ChainStore, options: Options) {
+    let Options { api_port, node_port, peers, seed, bits, throttle, max_block_size } = options;
+    let consensus = Arc::new(consensus);
+    let mut blockchain = match store.load(Arc::clone(&consensus), bits) {
+        Ok(blockchain) => blockchain,
+        Err(err) => {
+            // Bozuk dosyanın üzerine yazmamak için program durdurulur
+            eprintln!("Failed to load {}: {}", store.path().display(), err);
+            std::process::exit(1);
+        }
+    };
+    println!("Loaded {} block(s) from {}", blockchain.blocks.len(), store.path().display());
+    blockchain.miner.throttle = throttle;
+    if let Some(seed) = seed {
+        // Sanal saat zincirin ucundan devam eder; çok iş parçacığında hangi nonce'un önce bulunacağı belli olmaz
+        let start = blockchain.blocks.last().map_or(SIMULATED_EPOCH, |tip| tip.timestamp + TARGET_BLOCK_TIME);
+        blockchain.clock = Arc::new(SimulatedClock::new(start, TARGET_BLOCK_TIME));
+        blockchain.miner.threads = 1;
+        println!("Deterministic run with seed {} (simulated clock)", seed);
+    }
+    if let Some(max_block_size) = max_block_size {
+        blockchain.mempool.max_block_size = max_block_size;
+    }
+    println!("Consensus: {}", consensus.name());
+    println!("Difficulty bits: {:#010x}", blockchain.bits);
+    println!("Mining with {} thread(s)", blockchain.miner.threads);
+
+    // Zincir, menü ile ağdan gelen mesajları işleyen iş parçacıkları arasında paylaşılır
+    let chain = Arc::new(Mutex::new(blockchain));
+    let node = node_port.map(|port| match Node::start(Arc::clone(&chain), store.clone(), port) {
+        Ok(node) => {
+            println!("Listening for peers on {}", node.address());
+            node.sync(&peers);
+            node
+        }
+        Err(err) => {
+            eprintln!("Failed to listen on port {}: {}", port, err);
+            std::process::exit(1);
+        }
+    });
+
+    // Oturumun rastgelelik kaynağı: tohumlu çalışmada tohumdan, değilse işletim sisteminden beslenir
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    // Bu oturumda işlemleri imzalamak için kullanılan anahtar çifti (cüzdan seçilmeden ödeme yapmak için)
+    let key = SigningKey::generate(&mut rng);
+    let address = wallet::address_of(&key.verifying_key());
+    println!("Your address: {}", address);
+    println!("Your public key: {}", to_hex(key.verifying_key().as_bytes()));
+
+    let api = api_port.map(|port| {
+        let api = Api::new(Arc::clone(&chain), store.clone(), node.clone(), address.clone());
+        match api.start(port) {
+            Ok(server) => {
+                println!("Serving JSON API on http://{}", server.address());
+                server
+            }
+            Err(err) => {
+                eprintln!("Failed to start API on port {}: {}", port, err);
+                std::process::exit(1);
+            }
+        }
+    });
+
+    // Ctrl+C madencilik sürerken sadece madenciliği iptal eder; menüdeyken programı kapatır
+    let mining: Arc<Mutex<Option<CancelToken>>> = Arc::default();
+    let handler_mining = Arc::clone(&mining);
+    ctrlc::set_handler(move || match handler_mining.lock().unwrap().as_ref() {
+        Some(cancel) => cancel.cancel(),
+        None => std::process::exit(130),
+    })
+    .expect("Failed to set Ctrl+C handler");
+
+    // Kurcalama senaryosunun üzerinde çalıştığı zincir kopyası (yeniden kazılana kadar)
+    let mut simulation: Option<Blockchain<C>> = None;
 
     loop {
         // Menü seçenekleri gösterilir
         println!("\nAvailable commands:");
         println!("1. Add new transaction");
-        println!("2. Display blockchain");
-        println!("3. Verify blockchain");
-        println!("4. Exit");
+        println!("2. Mine block");
+        println!("3. Display blockchain");
+        println!("4. Verify blockchain");
+        println!("5. Prove transaction inclusion");
+        println!("6. Check balance");
+        println!("7. Show mempool");
+        println!("8. Tamper with a block (simulation)");
+        println!("9. Re-mine simulation from a block");
+        println!("10. Simulate 51% attack");
+        println!("11. Create wallet");
+        println!("12. List wallets");
+        println!("13. Send from wallet");
+        println!("14. Explore chain");
+        println!("15. Export chain (JSON/CSV)");
+        println!("16. Import chain (JSON/CSV)");
+        println!("17. Lock coins in a script (multisig/timelock)");
+        println!("18. Spend from a script");
+        println!("19. Statistics");
+        println!("20. Exit");
         print!("Enter your choice: ");
         io::stdout().flush().unwrap();
 
         let mut choice = String::new();
-        io::stdin().read_line(&mut choice).expect("Failed to read input");
+        if io::stdin().read_line(&mut choice).expect("Failed to read input") == 0 {
+            // Girdi kapandı (ör. betikten çalıştırıldı); API açıksa program sunucu olarak çalışmaya devam eder
+            if let Some(api) = api {
+                println!("\nInput closed, serving the API only (press Ctrl+C to exit)");
+                api.join();
+            }
+            break;
+        }
 
         match choice.trim() {
             "1" => {
-                // Yeni işlem verisi istenir ve blok olarak zincire eklenir
-                print!("Enter transaction data: ");
-                io::stdout().flush().unwrap();
-                let mut data = String::new();
-                io::stdin().read_line(&mut data).expect("Failed to read input");
-                println!("Mining block...");
-                blockchain.add_block(data.trim().to_string());
-                println!("Block mined and added to chain!");
+                // Ödemeler bu oturumun anahtarıyla imzalanır
+                send_payments(&chain, node.as_ref(), &key);
             }
             "2" => {
+                // Havuzdaki en yüksek ücretli işlemlerle aday blok hazırlanır; havuz boşsa sadece blok ödülü kazılır
+                // Madencilik sırasında ağdan gelen bloklar işlenebilsin diye zincirin kilidi bırakılır
+                let (block, miner, consensus) = {
+                    let mut blockchain = chain.lock().unwrap();
+                    match blockchain.block_template(&address) {
+                        Ok(block) => (block, blockchain.miner.clone(), Arc::clone(&blockchain.consensus)),
+                        Err(err) => {
+                            println!("Failed to assemble block: {}", err);
+                            continue;
+                        }
+                    }
+                };
+                println!("Assembled block with {} transaction(s)", block.transactions.len() - 1);
+
+                // Madencilik arka planda yürür; menü iş parçacığı ilerlemeyi kanaldan okuyup durum satırını günceller
+                println!("Mining block... (press Ctrl+C to cancel)");
+                let cancel = CancelToken::default();
+                *mining.lock().unwrap() = Some(cancel.clone());
+                let mut mined = None;
+                let mut failure = None;
+                for event in miner.spawn(consensus, block, cancel) {
+                    match event {
+                        MiningEvent::Progress(progress) => {
+                            print!(
+                                "\r{} hash(es), {:.0} H/s, best hash has {} leading zero bit(s)   ",
+                                progress.attempts, progress.hash_rate, progress.best_leading_zeros
+                            );
+                            io::stdout().flush().unwrap();
+                        }
+                        MiningEvent::Finished(block, stats) => mined = Some((block, stats)),
+                        MiningEvent::Cancelled => {}
+                        MiningEvent::Failed(err) => failure = Some(err),
+                    }
+                }
+                *mining.lock().unwrap() = None;
+                println!();
+
+                if let Some(err) = failure {
+                    println!("Failed to seal block: {}", err);
+                    continue;
+                }
+                let Some((block, stats)) = mined else {
+                    println!("Mining cancelled.");
+                    continue;
+                };
+                let events = match publish_block(&chain, &store, node.as_ref(), *block, Some(stats)) {
+                    Ok(events) => events,
+                    Err(err) => {
+                        println!("Block rejected: {}", err);
+                        continue;
+                    }
+                };
+                for event in &events {
+                    println!("{}", event);
+                }
+                println!("Block mined and added to chain! Reward sent to {}", address);
+                println!(
+                    "{} hash(es) in {:.2?} ({:.0} H/s)",
+                    stats.attempts, stats.elapsed, stats.hash_rate()
+                );
+            }
+            "3" => {
                 // Zincir görüntülenir
                 println!("\nBlockchain contents:");
-                blockchain.display();
+                chain.lock().unwrap().display();
+                if let Some(node) = &node {
+                    println!("Node {} connected to {} peer(s)", node.address(), node.peer_count());
+                }
             }
-            "3" => {
+            "4" => {
                 // Zincirin geçerli olup olmadığı kontrol edilir
                 println!("\nVerifying blockchain integrity...");
-                if blockchain.is_chain_valid() {
+                let report = chain.lock().unwrap().validate();
+                if report.is_valid() {
                     println!("Blockchain is valid!");
                 } else {
-                    println!("Blockchain validation failed!");
+                    // Her ihlal, bloğun sırası ve nedeniyle listelenir
+                    println!("Blockchain validation failed: {}", report);
                 }
             }
-            "4" => {
+            "5" => {
+                // Hafif istemci senaryosu: işlemin blokta olduğu sadece başlık ve Merkle yolu ile doğrulanır
+                let block_index = prompt("Block index: ").parse::<usize>().ok();
+                let tx_index = prompt("Transaction index: ").parse::<usize>().unwrap_or(usize::MAX);
+                let blockchain = chain.lock().unwrap();
+                let block = block_index.and_then(|i| blockchain.blocks.get(i));
+                let Some((block, proof)) = block.and_then(|block| block.prove(tx_index).map(|proof| (block, proof))) else {
+                    println!("No such transaction.");
+                    continue;
+                };
+                // Hafif istemciye bloğun tamamı değil sadece 84 byte'lık ikili başlık gönderilir
+                let raw_header = block.header().encode();
+                println!("Header ({} bytes): {}", raw_header.len(), to_hex(&raw_header));
+                println!("Merkle proof ({} sibling hash(es)):", proof.siblings.len());
+                for sibling in &proof.siblings {
+                    println!("  {}", to_hex(sibling));
+                }
+                // İstemci başlığı çözer, mührü (iş kanıtı ya da yetkili imzası) kontrol eder
+                // ve yolu başlıktaki Merkle köküne karşı doğrular
+                let header = match BlockHeader::decode(&raw_header) {
+                    Ok(header) => header,
+                    Err(err) => {
+                        println!("Header could not be decoded: {}", err);
+                        continue;
+                    }
+                };
+                // Yetki kanıtında imza başlıkla birlikte gönderilir; mühür başlığın hash'i üzerinden doğrulanır
+                if let Err(err) = blockchain.consensus.verify_seal(&Block { hash: header.hash(), ..block.clone() }) {
+                    println!("Header seal is invalid: {}", err);
+                    continue;
+                }
+                let leaf = block.transactions[tx_index].hash();
+                if merkle::verify_proof(&leaf, &proof, &header.merkle_root) {
+                    println!("Transaction is included in block {}.", block.index);
+                } else {
+                    println!("Proof does not match the block's Merkle root!");
+                }
+            }
+            "6" => {
+                // Adres boş bırakılırsa bu oturumun adresi sorgulanır
+                let query = prompt("Address (empty for yours): ");
+                let query = if query.is_empty() { address.clone() } else { query };
+                println!("Balance of {}: {}", query, chain.lock().unwrap().balance_of(&query));
+            }
+            "7" => {
+                // Bekleyen işlemler ücret oranıyla listelenir
+                let blockchain = chain.lock().unwrap();
+                println!("\nMempool: {} transaction(s), {} byte(s)", blockchain.mempool.len(), blockchain.mempool.total_size());
+                if blockchain.mempool.is_empty() {
+                    println!("  (no pending transactions)");
+                }
+                for entry in blockchain.mempool.entries() {
+                    println!(
+                        "  {} fee {} size {} B ({:.3}/B)",
+                        entry.transaction.txid(),
+                        entry.fee,
+                        entry.size,
+                        entry.fee_rate()
+                    );
+                }
+            }
+            "8" => {
+                // Atölye senaryosu: geçmişteki bir işlemin çıktısı zincirin kopyası üzerinde değiştirilir
+                // Art arda yapılan değişiklikler aynı kopyada birikir; kopya yeniden kazımayla birlikte kapanır
+                let simulation = simulation.get_or_insert_with(|| chain.lock().unwrap().clone());
+                let block_index = prompt("Block index: ").parse::<usize>().unwrap_or(usize::MAX);
+                let tx_index = prompt("Transaction index: ").parse::<usize>().unwrap_or(usize::MAX);
+                let output_index = prompt("Output index: ").parse::<usize>().unwrap_or(usize::MAX);
+                let amount = prompt("New amount (empty to keep): ").parse::<u64>().ok();
+                let recipient = Some(prompt("New recipient (empty to keep): ")).filter(|recipient| !recipient.is_empty());
+                if let Err(err) = simulation.tamper_output(block_index, tx_index, output_index, amount, recipient) {
+                    println!("Tampering failed: {}", err);
+                    continue;
+                }
+                println!("Block {} edited in the simulation copy (the real chain is untouched).", block_index);
+                println!("Verification of the simulation: {}", simulation.validate());
+            }
+            "9" => {
+                // Değiştirilen bloktan itibaren kopya yeniden kazılır; sonra dürüst bir düğüme sunulup zincir seçimi gösterilir
+                let Some(mut rewritten) = simulation.take() else {
+                    println!("Nothing to re-mine; tamper with a block first.");
+                    continue;
+                };
+                let from = prompt("Re-mine from block index: ").parse::<usize>().unwrap_or(usize::MAX);
+                println!("Re-mining... (press Ctrl+C to cancel)");
+                let cancel = CancelToken::default();
+                *mining.lock().unwrap() = Some(cancel.clone());
+                let result = rewritten.remine_from(from, &cancel);
+                *mining.lock().unwrap() = None;
+                match result {
+                    Ok(stats) => println!("Re-mined {} block(s) with {} hash(es).", stats.blocks, stats.attempts),
+                    Err(err) => {
+                        println!("Re-mining failed: {}", err);
+                        simulation = Some(rewritten);
+                        continue;
+                    }
+                }
+                println!("Verification of the rewritten chain: {}", rewritten.validate());
+
+                // Dürüst düğüm yeniden yazılmış blokları ağdan alıyormuş gibi işler
+                let mut honest = chain.lock().unwrap().clone();
+                let honest_tip = honest.blocks.last().map(|tip| tip.hash);
+                match attack::present_blocks(&mut honest, &rewritten.blocks) {
+                    Ok(events) => {
+                        for event in &events {
+                            println!("{}", event);
+                        }
+                    }
+                    Err(err) => println!("Honest node rejected the rewritten chain: {}", err),
+                }
+                if honest.blocks.last().map(|tip| tip.hash) == honest_tip {
+                    println!(
+                        "Honest nodes keep their chain: the rewritten branch has work {} but theirs has {}.",
+                        rewritten.total_work(),
+                        honest.total_work()
+                    );
+                } else {
+                    println!("Honest nodes switched to the rewritten chain!");
+                }
+            }
+            "10" => {
+                // Saldırgan, hash gücünün verilen yüzdesiyle dürüst madencilerle yarışır (zincirin kopyası üzerinde)
+                // "nan" ve "inf" de f64 olarak çözülür; sonlu olmayan değerler varsayılana döner
+                let share = prompt("Attacker hash power % (default 60): ")
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|share| share.is_finite())
+                    .unwrap_or(60.0);
+                let confirmations = prompt("Confirmations merchants wait for (default 3): ").parse().unwrap_or(3);
+                let max_blocks = prompt("Give up after this many blocks (default 20): ").parse().unwrap_or(20);
+                let mut honest = chain.lock().unwrap().clone();
+                println!("Racing... (press Ctrl+C to cancel)");
+                let cancel = CancelToken::default();
+                *mining.lock().unwrap() = Some(cancel.clone());
+                let result = attack::majority_attack(&mut honest, share / 100.0, confirmations, max_blocks, &mut rng, &cancel);
+                *mining.lock().unwrap() = None;
+                let report = match result {
+                    Ok(report) => report,
+                    Err(err) => {
+                        println!("Attack simulation failed: {}", err);
+                        continue;
+                    }
+                };
+                println!(
+                    "Fork at height {}: honest miners found {} block(s), the attacker {} in secret.",
+                    report.fork_height, report.honest_blocks, report.attacker_blocks
+                );
+                if !report.published {
+                    println!("The attacker never overtook the honest chain and gave up.");
+                    continue;
+                }
+                for event in &report.events {
+                    println!("{}", event);
+                }
+                if report.succeeded() {
+                    println!(
+                        "Attack succeeded: {} confirmed honest block(s) were replaced by the attacker's heavier branch.",
+                        report.honest_blocks
+                    );
+                } else {
+                    println!("Honest nodes did not adopt the attacker's branch.");
+                }
+                println!(
+                    "Rewards on the resulting chain: attacker {}, honest miners {}.",
+                    honest.balance_of(attack::ATTACKER_ADDRESS),
+                    honest.balance_of(attack::HONEST_ADDRESS)
+                );
+                println!("Verification of the resulting chain: {}", honest.validate());
+            }
+            "11" => {
+                // Yeni anahtar çifti üretilir ve parolayla şifrelenip keystore'a yazılır
+                let name = prompt("Wallet name: ");
+                if name.is_empty() {
+                    println!("Wallet name cannot be empty.");
+                    continue;
+                }
+                let passphrase = prompt("Passphrase: ");
+                if prompt("Repeat passphrase: ") != passphrase {
+                    println!("Passphrases do not match.");
+                    continue;
+                }
+                match keystore.create(&name, &passphrase) {
+                    Ok(wallet) => println!("Created wallet {} with address {}", wallet.name, wallet.address),
+                    Err(err) => println!("Failed to create wallet: {}", err),
+                }
+            }
+            "12" => {
+                // Adresler açık saklandığı için listelemek parola gerektirmez
+                if keystore.wallets().is_empty() {
+                    println!("No wallets in {}.", keystore.path().display());
+                    continue;
+                }
+                let blockchain = chain.lock().unwrap();
+                for wallet in keystore.wallets() {
+                    println!("  {} {} balance {}", wallet.name, wallet.address, blockchain.balance_of(&wallet.address));
+                }
+            }
+            "13" => {
+                // Cüzdanın kilidi parolayla açılır ve ödemeler cüzdanın anahtarıyla imzalanır
+                let name = prompt("Wallet name: ");
+                let passphrase = prompt("Passphrase: ");
+                match keystore.unlock(&name, &passphrase) {
+                    Ok(wallet) => {
+                        println!("Sending from {} ({})", wallet.name, wallet.address);
+                        send_payments(&chain, node.as_ref(), &wallet.key);
+                    }
+                    Err(err) => println!("Failed to unlock wallet: {}", err),
+                }
+            }
+            "14" => {
+                // İndeksli sorgular ve sayfalı tablo görünümü
+                explore(&chain);
+            }
+            "15" => {
+                // Biçim dosya uzantısından seçilir
+                let path = PathBuf::from(prompt("Export to (.json or .csv): "));
+                let Some(format) = ChainFormat::from_path(&path) else {
+                    println!("Unknown format; use a .json or .csv file name.");
+                    continue;
+                };
+                let blockchain = chain.lock().unwrap();
+                match blockchain.export(&path, format) {
+                    Ok(()) => println!("Exported {} block(s) to {}", blockchain.blocks.len(), path.display()),
+                    Err(err) => println!("Export failed: {}", err),
+                }
+            }
+            "16" => {
+                // İçe aktarılan zincir önce kendi başına doğrulanır, sonra bloklar ağdan gelmiş gibi zincire sunulur:
+                // daha çok işe sahipse en çok iş kuralıyla ana zincir olur, değilse yan dal olarak kalır
+                let path = PathBuf::from(prompt("Import from (.json or .csv): "));
+                let Some(format) = ChainFormat::from_path(&path) else {
+                    println!("Unknown format; use a .json or .csv file name.");
+                    continue;
+                };
+                let (consensus, bits) = {
+                    let blockchain = chain.lock().unwrap();
+                    (Arc::clone(&blockchain.consensus), blockchain.bits)
+                };
+                let imported = match export::import(&path, format, consensus, bits) {
+                    Ok(imported) => imported,
+                    Err(err) => {
+                        println!("Import failed: {}", err);
+                        continue;
+                    }
+                };
+                println!(
+                    "Imported chain is valid: {} block(s), total work {}",
+                    imported.blocks.len(),
+                    imported.total_work()
+                );
+                if !prompt("Submit the imported blocks to the current chain? (y/N): ").eq_ignore_ascii_case("y") {
+                    continue;
+                }
+                for block in imported.blocks {
+                    if chain.lock().unwrap().contains(&block.hash) {
+                        continue;
+                    }
+                    match publish_block(&chain, &store, node.as_ref(), block, None) {
+                        Ok(events) => events.iter().for_each(|event| println!("{}", event)),
+                        Err(err) => {
+                            println!("Block rejected: {}", err);
+                            break;
+                        }
+                    }
+                }
+            }
+            "17" => {
+                // Ödeme oturumun anahtarıyla yapılır; çıktıyı betikteki katılımcılar harcar
+                lock_in_script(&chain, node.as_ref(), &keystore, &key);
+            }
+            "18" => {
+                spend_from_script(&chain, node.as_ref(), &keystore, &key);
+            }
+            "19" => {
+                // Zincir istatistikleri; blok başına ölçümler istenirse CSV'ye yazılır
+                let blockchain = chain.lock().unwrap();
+                println!("{}", blockchain.statistics());
+                drop(blockchain);
+                let path = prompt("Write per-block metrics to CSV (file name, empty to skip): ");
+                if path.is_empty() {
+                    continue;
+                }
+                let blockchain = chain.lock().unwrap();
+                match blockchain.export_metrics(Path::new(&path)) {
+                    Ok(()) => println!("Wrote metrics for {} block(s) to {}", blockchain.blocks.len(), path),
+                    Err(err) => println!("Failed to write metrics: {}", err),
+                }
+            }
+            "20" => {
                 // Programdan çıkış yapılır
                 println!("Exiting blockchain simulator...");
                 break;
@@ -184,3 +1322,145 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use difficulty::{bits_to_target, EASIEST_BITS, RETARGET_INTERVAL};
+    use rand::rngs::OsRng;
+
+    impl Blockchain {
+        // İş kanıtıyla ve belirli bir başlangıç zorluğu ile boş blockchain oluşturur
+        // (Testlerde kolay bir hedef seçilerek bloklar milisaniyeler içinde bulunabilir)
+        // Zaman damgaları sanal saatten okunur; testlerin sonucu sistem saatine bağlı olmaz
+        pub(crate) fn with_difficulty(bits: u32) -> Blockchain {
+            let mut blockchain = Blockchain::with_consensus(Arc::new(ProofOfWork), bits);
+            blockchain.clock = Arc::new(SimulatedClock::new(SIMULATED_EPOCH, TARGET_BLOCK_TIME));
+            blockchain
+        }
+    }
+
+    // Bloğu iptal edilmeden sonuna kadar tek iş parçacığıyla kazar (aynı blok her seferinde aynı nonce'u bulur)
+    pub(crate) fn mine(block: &mut Block) {
+        Miner { threads: 1, throttle: None }.mine_with(block, &CancelToken::default(), |_| {}).unwrap();
+    }
+
+    // Aday bloğu hazırlar, kazar ve zincire ekler
+    pub(crate) fn mine_block(blockchain: &mut Blockchain, miner: &str, transactions: Vec<Transaction>) -> Result<(), BlockError> {
+        let mut block = blockchain.candidate_block(miner, transactions)?;
+        mine(&mut block);
+        blockchain.add_block(block)
+    }
+
+    // `blocks` adet boş blok kazılmış, ödülleri `miner` adresine giden zincir
+    pub(crate) fn chain_with(blocks: usize, miner: &str) -> Blockchain {
+        let mut blockchain = Blockchain::with_difficulty(EASIEST_BITS);
+        for _ in 0..blocks {
+            mine_block(&mut blockchain, miner, Vec::new()).unwrap();
+        }
+        blockchain
+    }
+
+    // Adresine `rewards` adet blok ödülü kazanılmış anahtar, adresi ve zincir
+    pub(crate) fn funded_chain(rewards: usize) -> (Blockchain, SigningKey, String) {
+        let key = SigningKey::generate(&mut OsRng);
+        let owner = wallet::address_of(&key.verifying_key());
+        (chain_with(rewards, &owner), key, owner)
+    }
+
+    // Testin kendi geçici dosya yolu; süreç numarası aynı anda çalışan test süreçlerini ayırır
+    // Önceki çalıştırmadan kalan dosya silinir
+    pub(crate) fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("blockchain_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    // Geçici dosyaya yazan boş zincir deposu
+    pub(crate) fn temp_store(name: &str) -> ChainStore {
+        ChainStore::new(temp_path(&format!("{}.jsonl", name)))
+    }
+
+    // Anahtarın çıktılarından alıcıya ödeme yapan tek işlemlik blok içeriği
+    fn transfer(blockchain: &Blockchain, key: &SigningKey, recipient: &str, amount: u64) -> Vec<Transaction> {
+        let payment = vec![TxOutput { amount, address: recipient.to_string(), script: None }];
+        vec![blockchain.utxos.build_transaction(key, payment, 0).unwrap()]
+    }
+
+
+    #[test]
+    fn mined_blocks_meet_their_target() {
+        let (mut blockchain, key, miner) = funded_chain(1);
+        let transactions = transfer(&blockchain, &key, "bob", 5);
+        mine_block(&mut blockchain, &miner, transactions).unwrap();
+        assert!(blockchain.blocks.iter().all(|block| blockchain.consensus.verify_seal(block).is_ok()));
+        assert!(blockchain.validate().is_valid());
+        assert_eq!(blockchain.balance_of("bob"), 5);
+        assert_eq!(blockchain.balance_of(&miner), 2 * utxo::BLOCK_REWARD - 5);
+    }
+
+    #[test]
+    fn double_spend_across_blocks_is_rejected() {
+        let (mut blockchain, key, miner) = funded_chain(1);
+        let transactions = transfer(&blockchain, &key, "bob", 5);
+        mine_block(&mut blockchain, &miner, transactions.clone()).unwrap();
+        let result = mine_block(&mut blockchain, &miner, transactions);
+        assert!(matches!(result, Err(BlockError::Ledger(LedgerError::MissingOutput { .. }))));
+        assert_eq!(blockchain.blocks.len(), 2);
+    }
+
+    #[test]
+    fn forged_signature_invalidates_chain() {
+        let (mut blockchain, key, miner) = funded_chain(1);
+        let transactions = transfer(&blockchain, &key, "bob", 5);
+        mine_block(&mut blockchain, &miner, transactions).unwrap();
+        // İşlem değiştirilip Merkle kökü güncellense ve blok yeniden kazılsa bile imza artık tutmaz
+        let block = &mut blockchain.blocks[1];
+        block.transactions[1].outputs[0].amount = 50;
+        block.merkle_root = block.calculate_merkle_root();
+        mine(block);
+        assert!(!blockchain.validate().is_valid());
+    }
+
+    // mine_block ile aynı işi yapar, ancak zaman damgası elle verilir
+    fn add_block_at(blockchain: &mut Blockchain, timestamp: u64) {
+        let mut block = blockchain.candidate_block("miner", Vec::new()).unwrap();
+        block.timestamp = timestamp;
+        mine(&mut block);
+        blockchain.add_block(block).unwrap();
+    }
+
+    #[test]
+    fn difficulty_follows_retarget_schedule() {
+        let start = difficulty::retarget(EASIEST_BITS, 1, 4); // Ayarlamaya yer kalsın diye biraz daha zor başla
+        let mut blockchain = Blockchain::with_difficulty(start);
+        // Bloklar hedeflenenden iki kat hızlı geliyor
+        for i in 0..=RETARGET_INTERVAL as u64 {
+            add_block_at(&mut blockchain, 1_000 + i * TARGET_BLOCK_TIME / 2);
+        }
+        let retargeted = &blockchain.blocks[RETARGET_INTERVAL];
+        assert!(bits_to_target(retargeted.bits) < bits_to_target(start));
+        assert!(blockchain.blocks[..RETARGET_INTERVAL].iter().all(|b| b.bits == start));
+        assert!(blockchain.validate().is_valid());
+    }
+
+    #[test]
+    fn block_with_wrong_difficulty_is_rejected() {
+        let mut blockchain = Blockchain::with_difficulty(EASIEST_BITS);
+        add_block_at(&mut blockchain, 1_000);
+        // Takvimdekinden farklı (daha kolay) zorlukla kazılmış blok
+        let mut block = blockchain.candidate_block("miner", Vec::new()).unwrap();
+        block.bits = 0x2100ffff;
+        mine(&mut block);
+        assert!(matches!(blockchain.add_block(block.clone()), Err(BlockError::WrongDifficulty)));
+        blockchain.blocks.push(block);
+        assert!(!blockchain.validate().is_valid());
+    }
+
+    #[test]
+    fn hash_above_target_is_rejected() {
+        let mut block = Block::new(0, ZERO_HASH, Vec::new(), DEFAULT_BITS, 0);
+        block.hash = [0xff; 32];
+        assert!(matches!(ProofOfWork.verify_seal(&block), Err(SealError::InsufficientWork { .. })));
+    }
+}
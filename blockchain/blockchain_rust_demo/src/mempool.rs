@@ -0,0 +1,317 @@
+// Bekleyen işlem havuzu (mempool)
+// İşlemler önce havuza girer; zincirin ucuna ve havuzdaki diğer işlemlere göre doğrulanır
+// Madenci aday bloğu, blok boyutu sınırına kadar en yüksek ücret oranlı (ücret / byte) işlemlerle doldurur
+// Havuz çok eskiyen işlemleri ve boyut sınırını aşınca en düşük ücret oranlı işlemleri çıkarır
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::consensus::Consensus;
+use crate::transaction::Transaction;
+use crate::utxo::{LedgerError, UtxoSet};
+use crate::{Block, Blockchain};
+
+// Bir bloğa girebilecek işlemlerin toplam boyutu (byte, coinbase hariç)
+pub const DEFAULT_MAX_BLOCK_SIZE: usize = 4_000;
+
+// Havuzdaki işlemlerin toplam boyut sınırı (byte)
+pub const DEFAULT_MAX_POOL_SIZE: usize = 100_000;
+
+// Bu kadar saniyeden uzun bekleyen işlemler havuzdan çıkarılır
+pub const DEFAULT_MAX_AGE: u64 = 600;
+
+// Bir işlem havuza alınamadığında dönen hatalar
+#[derive(Debug)]
+pub enum MempoolError {
+    Duplicate,           // İşlem zaten havuzda
+    Ledger(LedgerError), // İşlem zincire ve havuza göre geçersiz (çakışan harcama dahil)
+    FeeTooLow,           // Havuz dolu ve işlemin ücret oranı en düşük olanlardan
+}
+
+impl Display for MempoolError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MempoolError::Duplicate => write!(f, "transaction is already in the mempool"),
+            MempoolError::Ledger(err) => write!(f, "{}", err),
+            MempoolError::FeeTooLow => write!(f, "mempool is full and the fee rate is too low"),
+        }
+    }
+}
+
+impl std::error::Error for MempoolError {}
+
+impl From<LedgerError> for MempoolError {
+    fn from(err: LedgerError) -> Self {
+        MempoolError::Ledger(err)
+    }
+}
+
+// Havuzdaki bir işlem ve kabul edildiği andaki bilgileri
+#[derive(Clone)]
+pub struct MempoolEntry {
+    pub transaction: Transaction,
+    pub fee: u64,      // Girdiler - çıktılar
+    pub size: usize,   // İşlemin serileştirilmiş boyutu (byte)
+    pub received: u64, // Havuza girdiği zaman (Unix saniyesi)
+}
+
+impl MempoolEntry {
+    // Byte başına ücret
+    pub fn fee_rate(&self) -> f64 {
+        self.fee as f64 / self.size.max(1) as f64
+    }
+
+    // Ücret oranlarını bölme yapmadan (çapraz çarpımla) karşılaştırır
+    fn pays_more_than(&self, other: &MempoolEntry) -> bool {
+        self.fee as u128 * other.size as u128 > other.fee as u128 * self.size as u128
+    }
+}
+
+#[derive(Clone)]
+pub struct Mempool {
+    entries: Vec<MempoolEntry>, // Geliş sırasıyla; bir işlem kendinden önceki işlemlerin çıktılarını harcayabilir
+    pub max_block_size: usize,  // Aday bloğa alınacak işlemlerin toplam boyut sınırı
+    pub max_pool_size: usize,   // Havuzun toplam boyut sınırı
+    pub max_age: u64,           // Saniye cinsinden en uzun bekleme süresi
+}
+
+impl Default for Mempool {
+    fn default() -> Self {
+        Mempool {
+            entries: Vec::new(),
+            max_block_size: DEFAULT_MAX_BLOCK_SIZE,
+            max_pool_size: DEFAULT_MAX_POOL_SIZE,
+            max_age: DEFAULT_MAX_AGE,
+        }
+    }
+}
+
+impl Mempool {
+    pub fn entries(&self) -> &[MempoolEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn total_size(&self) -> usize {
+        self.entries.iter().map(|entry| entry.size).sum()
+    }
+
+    pub fn contains(&self, txid: &str) -> bool {
+        self.entries.iter().any(|entry| entry.transaction.txid() == txid)
+    }
+
+    // Zincirin ucundaki kümeye havuzdaki işlemler uygulanmış hali
+    // Cüzdan yeni işlemleri buradan hazırlar; böylece havuzda harcanmış çıktılar tekrar seçilmez
+    pub fn pending_utxos(&self, utxos: &UtxoSet) -> UtxoSet {
+        let mut staged = utxos.clone();
+        for entry in &self.entries {
+            // Havuzdaki işlemler her değişiklikten sonra yeniden doğrulandığı için hata beklenmez
+            let _ = staged.apply_transactions(std::slice::from_ref(&entry.transaction));
+        }
+        staged
+    }
+
+    // İşlemi doğrulayıp havuza ekler ve ücretini döndürür
+    // Havuzdaki başka bir işlemle aynı çıktıyı harcayan işlem çifte harcama olarak reddedilir
+    pub fn add(&mut self, transaction: Transaction, utxos: &UtxoSet, now: u64) -> Result<u64, MempoolError> {
+        let txid = transaction.txid();
+        if self.contains(&txid) {
+            return Err(MempoolError::Duplicate);
+        }
+        let fee = self.pending_utxos(utxos).validate_transaction(&transaction)?;
+        let size = transaction_size(&transaction);
+        self.entries.push(MempoolEntry { transaction, fee, size, received: now });
+        self.evict(utxos, now);
+        // Havuz doluysa yeni işlem en düşük ücret oranlıysa hemen çıkarılmış olabilir
+        if !self.contains(&txid) {
+            return Err(MempoolError::FeeTooLow);
+        }
+        Ok(fee)
+    }
+
+    // Süresi dolan işlemleri, ardından boyut sınırına inene kadar en düşük ücret oranlı işlemleri çıkarır
+    pub fn evict(&mut self, utxos: &UtxoSet, now: u64) {
+        self.entries.retain(|entry| now.saturating_sub(entry.received) <= self.max_age);
+        while self.total_size() > self.max_pool_size {
+            let cheapest = (0..self.entries.len())
+                .reduce(|cheapest, i| {
+                    // Oranlar eşitse daha yeni işlem çıkarılır
+                    if self.entries[cheapest].pays_more_than(&self.entries[i]) { i } else { cheapest }
+                })
+                .expect("non-empty pool exceeds its size limit");
+            self.entries.remove(cheapest);
+        }
+        self.revalidate(utxos); // Çıkarılan işlemlerin çıktılarını harcayan işlemler de geçersizleşir
+    }
+
+    // Zincirin ucu değiştiğinde (yeni blok, yeniden düzenleme) havuz baştan doğrulanır
+    // Bloğa girmiş ya da artık geçersiz olan işlemler çıkarılır
+    pub fn revalidate(&mut self, utxos: &UtxoSet) {
+        let mut staged = utxos.clone();
+        self.entries
+            .retain(|entry| staged.apply_transactions(std::slice::from_ref(&entry.transaction)).is_ok());
+    }
+
+    // Yeniden düzenlemede ana zincirden çıkan blokların işlemlerini havuza geri koyar
+    // Havuzdaki işlemler bu işlemlerin çıktılarını harcıyor olabilir; bu yüzden geri dönen işlemler
+    // bekleyenlerin önüne eklenir, havuz ancak ondan sonra baştan doğrulanır
+    pub fn restore(&mut self, transactions: Vec<Transaction>, utxos: &UtxoSet, now: u64) {
+        let waiting = std::mem::take(&mut self.entries);
+        for transaction in transactions {
+            let _ = self.add(transaction, utxos, now); // Yeni zincirde bloğa girmiş ya da çakışıyorsa atlanır
+        }
+        for entry in waiting {
+            if !self.contains(&entry.transaction.txid()) {
+                self.entries.push(entry);
+            }
+        }
+        self.evict(utxos, now);
+    }
+
+    // Blok boyutu sınırına sığan işlemleri ücret oranı yüksekten düşüğe seçer
+    // Ebeveyni henüz seçilmemiş bir işlem sonraki turlarda tekrar denenir
+    pub fn select(&self, utxos: &UtxoSet) -> Vec<Transaction> {
+        let mut candidates: Vec<&MempoolEntry> = self.entries.iter().collect();
+        candidates.sort_by(|a, b| b.fee_rate().total_cmp(&a.fee_rate()));
+
+        let mut staged = utxos.clone();
+        let mut selected = Vec::new();
+        let mut size = 0;
+        let mut progress = true;
+        while progress {
+            progress = false;
+            candidates.retain(|entry| {
+                if size + entry.size > self.max_block_size {
+                    return true;
+                }
+                if staged.apply_transactions(std::slice::from_ref(&entry.transaction)).is_err() {
+                    return true;
+                }
+                size += entry.size;
+                selected.push(entry.transaction.clone());
+                progress = true;
+                false
+            });
+        }
+        selected
+    }
+}
+
+// İşlemin ağda ve diskte kapladığı yer: JSON olarak serileştirilmiş boyutu
+fn transaction_size(transaction: &Transaction) -> usize {
+    serde_json::to_vec(transaction).map_or(0, |bytes| bytes.len())
+}
+
+impl<C: Consensus> Blockchain<C> {
+    // İşlemi zincirin ucuna göre doğrulayıp havuza ekler
+    pub fn submit_transaction(&mut self, transaction: Transaction) -> Result<u64, MempoolError> {
+        self.mempool.add(transaction, &self.utxos, self.clock.now())
+    }
+
+    // Havuzdan en kârlı işlemleri seçerek madenciliğe hazır aday blok oluşturur
+    pub fn block_template(&mut self, miner: &str) -> Result<Block, LedgerError> {
+        self.mempool.evict(&self.utxos, self.clock.now());
+        let transactions = self.mempool.select(&self.utxos);
+        self.candidate_block(miner, transactions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{funded_chain, mine};
+    use crate::transaction::TxOutput;
+    use crate::utxo::BLOCK_REWARD;
+    use ed25519_dalek::SigningKey;
+
+    // Havuzdaki bekleyen durumdan ödeme işlemi hazırlar
+    fn payment(blockchain: &Blockchain, key: &SigningKey, recipient: &str, amount: u64, fee: u64) -> Transaction {
+        let payment = vec![TxOutput { amount, address: recipient.to_string(), script: None }];
+        let utxos = blockchain.mempool.pending_utxos(&blockchain.utxos);
+        utxos.build_transaction(key, payment, fee).unwrap()
+    }
+
+    #[test]
+    fn block_template_orders_by_fee_rate_within_block_size() {
+        let (mut blockchain, key, _) = funded_chain(3);
+        // Her işlem bir ödülün tamamını harcar (para üstü yok), böylece işlemler birbirinden bağımsızdır
+        for (recipient, fee) in [("low", 1), ("high", 5), ("mid", 3)] {
+            let transaction = payment(&blockchain, &key, recipient, BLOCK_REWARD - fee, fee);
+            assert_eq!(blockchain.submit_transaction(transaction).unwrap(), fee);
+        }
+        // Sadece iki işlemin sığacağı blok boyutu
+        let sizes: Vec<usize> = blockchain.mempool.entries().iter().map(|entry| entry.size).collect();
+        blockchain.mempool.max_block_size = sizes[1] + sizes[2];
+
+        let mut block = blockchain.block_template("miner").unwrap();
+        let recipients: Vec<&str> = block.transactions[1..].iter().map(|tx| tx.outputs[0].address.as_str()).collect();
+        assert_eq!(recipients, ["high", "mid"]);
+        assert_eq!(block.transactions[0].outputs[0].amount, BLOCK_REWARD + 8);
+
+        // Bloğa giren işlemler havuzdan çıkar, kalan işlem bir sonraki bloğu bekler
+        mine(&mut block);
+        blockchain.add_block(block).unwrap();
+        assert_eq!(blockchain.mempool.len(), 1);
+        assert_eq!(blockchain.balance_of("miner"), BLOCK_REWARD + 8);
+    }
+
+    #[test]
+    fn conflicting_spend_is_rejected() {
+        let (mut blockchain, key, _) = funded_chain(1);
+        let first = payment(&blockchain, &key, "bob", 10, 1);
+        // Havuzu görmeden hazırlanmış ikinci işlem aynı çıktıyı harcar
+        let second = blockchain
+            .utxos
+            .build_transaction(&key, vec![TxOutput { amount: 10, address: String::from("carol"), script: None }], 2)
+            .unwrap();
+        blockchain.submit_transaction(first.clone()).unwrap();
+        assert!(matches!(blockchain.submit_transaction(first), Err(MempoolError::Duplicate)));
+        assert!(matches!(
+            blockchain.submit_transaction(second),
+            Err(MempoolError::Ledger(LedgerError::MissingOutput { .. }))
+        ));
+    }
+
+    #[test]
+    fn unconfirmed_change_can_be_spent() {
+        let (mut blockchain, key, _) = funded_chain(1);
+        let parent = payment(&blockchain, &key, "bob", 10, 1);
+        blockchain.submit_transaction(parent).unwrap();
+        // Kalan tek çıktı havuzdaki işlemin para üstü
+        let child = payment(&blockchain, &key, "carol", 10, 1);
+        blockchain.submit_transaction(child).unwrap();
+        assert_eq!(blockchain.mempool.select(&blockchain.utxos).len(), 2);
+    }
+
+    #[test]
+    fn evicts_old_and_cheap_transactions() {
+        let (blockchain, key, _) = funded_chain(3);
+        let mut mempool = Mempool::default();
+        let utxos = &blockchain.utxos;
+        let build = |mempool: &Mempool, fee| {
+            let payment = vec![TxOutput { amount: BLOCK_REWARD - fee, address: String::from("bob"), script: None }];
+            mempool.pending_utxos(utxos).build_transaction(&key, payment, fee).unwrap()
+        };
+
+        let old = build(&mempool, 1);
+        mempool.add(old, utxos, 0).unwrap();
+        mempool.evict(utxos, DEFAULT_MAX_AGE + 1);
+        assert!(mempool.is_empty());
+
+        let rich = build(&mempool, 9);
+        mempool.add(rich, utxos, 0).unwrap();
+        mempool.max_pool_size = mempool.total_size();
+        let cheap = build(&mempool, 1);
+        assert!(matches!(mempool.add(cheap, utxos, 0), Err(MempoolError::FeeTooLow)));
+        let richer = build(&mempool, 20);
+        mempool.add(richer, utxos, 0).unwrap();
+        assert_eq!(mempool.len(), 1);
+        assert_eq!(mempool.entries()[0].fee, 20);
+    }
+}
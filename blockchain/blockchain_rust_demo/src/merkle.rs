@@ -0,0 +1,111 @@
+// Merkle ağacı: bloktaki işlemlerin hash'lerini ikişer ikişer birleştirerek tek bir köke indirger
+// Blok başlığında sadece kök saklanır; bir işlemin blokta olduğunu kanıtlamak için
+// tüm blok yerine yalnızca yol üzerindeki kardeş hash'ler (log2(n) adet) yeterlidir
+
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+// Bir işlemin ağaçtaki yerini kanıtlayan yol
+pub struct MerkleProof {
+    pub index: usize,       // İşlemin bloktaki sırası (her seviyede sol/sağ yönü belirler)
+    pub siblings: Vec<Hash>, // Yapraktan köke kadar her seviyedeki kardeş hash
+}
+
+// İki düğümü birleştirerek üst düğümü hesaplar
+fn hash_pair(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+// Bir seviyedeki düğümlerden bir üst seviyeyi üretir
+// Tek sayıda düğüm varsa sonuncusu kendisiyle eşlenir (Bitcoin'deki gibi)
+fn next_level(level: &[Hash]) -> Vec<Hash> {
+    level
+        .chunks(2)
+        .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+        .collect()
+}
+
+// Yaprakların (işlem hash'lerinin) Merkle kökünü hesaplar; boş blokta kök sıfırlardan oluşur
+pub fn merkle_root(leaves: &[Hash]) -> Hash {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+// index sıradaki yaprak için kanıt üretir (index geçersizse None)
+pub fn prove(leaves: &[Hash], index: usize) -> Option<MerkleProof> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut siblings = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut position = index;
+    while level.len() > 1 {
+        let sibling = position ^ 1; // Çift sıradakinin kardeşi sağında, tek sıradakinin solundadır
+        siblings.push(*level.get(sibling).unwrap_or(&level[position]));
+        level = next_level(&level);
+        position /= 2;
+    }
+    Some(MerkleProof { index, siblings })
+}
+
+// Yaprak ve kanıttan kökü yeniden hesaplar, beklenen kökle karşılaştırır
+pub fn verify_proof(leaf: &Hash, proof: &MerkleProof, root: &Hash) -> bool {
+    let mut hash = *leaf;
+    let mut position = proof.index;
+    for sibling in &proof.siblings {
+        hash = if position.is_multiple_of(2) {
+            hash_pair(&hash, sibling)
+        } else {
+            hash_pair(sibling, &hash)
+        };
+        position /= 2;
+    }
+    &hash == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u8) -> Vec<Hash> {
+        (0..count).map(|i| Sha256::digest([i]).into()).collect()
+    }
+
+    #[test]
+    fn root_of_single_leaf_is_the_leaf() {
+        let leaves = leaves(1);
+        assert_eq!(merkle_root(&leaves), leaves[0]);
+    }
+
+    #[test]
+    fn proofs_verify_for_every_leaf() {
+        for count in 1..=7 {
+            let leaves = leaves(count);
+            let root = merkle_root(&leaves);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = prove(&leaves, index).unwrap();
+                assert!(verify_proof(leaf, &proof, &root));
+            }
+        }
+    }
+
+    #[test]
+    fn proof_fails_for_other_leaf_or_root() {
+        let leaves = leaves(5);
+        let root = merkle_root(&leaves);
+        let proof = prove(&leaves, 2).unwrap();
+        assert!(!verify_proof(&leaves[3], &proof, &root));
+        assert!(!verify_proof(&leaves[2], &proof, &merkle_root(&leaves[..4])));
+        assert!(prove(&leaves, 5).is_none());
+    }
+}
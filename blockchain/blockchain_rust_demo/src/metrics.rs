@@ -0,0 +1,213 @@
+// Blok ve zincir ölçümleri: zorluk ayarlarını karşılaştırmak (benchmark) için
+// Bloğun kendisinden çıkarılabilenler (boyut, iki blok arasındaki süre, zorluk) her blok için hesaplanır;
+// kazma süresi ve deneme sayısı ise blokta yazmaz, sadece bu oturumda bu düğümün kazdığı bloklar için kaydedilir
+// Blok başına ölçümler CSV olarak dışa aktarılabilir
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::consensus::Consensus;
+use crate::difficulty::TARGET_BLOCK_TIME;
+use crate::merkle::Hash;
+use crate::miner::MiningStats;
+use crate::{to_hex, Block, Blockchain};
+
+// Bu oturumda kazılan blokların madencilik istatistikleri (blok hash'ine göre)
+// Zincir yeniden düzenlenince kayıtlar silinmez; bloğu ana zincire dönerse ölçümleri yine görünür
+pub type MiningLog = BTreeMap<Hash, MiningStats>;
+
+// Bir bloğun ölçümleri (CSV'de bir satır)
+#[derive(Serialize)]
+pub struct BlockMetrics {
+    pub height: usize,
+    pub hash: String,
+    pub timestamp: u64,
+    pub interval: Option<u64>,       // Önceki bloktan bu yana geçen süre (saniye, genesis'te boş)
+    pub bits: u32,
+    pub work: u128,                  // Bloğun uzlaşma kurallarına göre ağırlığı (iş kanıtında beklenen deneme sayısı)
+    pub transactions: usize,
+    pub size: usize,                 // Serileştirilmiş blok boyutu (byte)
+    pub attempts: Option<u64>,       // Denenen hash sayısı (başka düğümün kazdığı blokta boş)
+    pub mining_seconds: Option<f64>, // Kazma süresi
+    pub hash_rate: Option<f64>,      // Saniyedeki hash sayısı
+}
+
+// Zincirin geneline ait istatistikler
+pub struct ChainStatistics {
+    pub blocks: usize,
+    pub mined_here: usize,                     // Bu oturumda bu düğümün kazdığı ana zincir blokları
+    pub average_interval: Option<f64>,         // Bloklar arası ortalama süre (saniye)
+    pub interval_range: Option<(u64, u64)>,    // En kısa ve en uzun blok aralığı
+    pub total_attempts: u64,                   // Kazılan bloklarda denenen toplam hash sayısı
+    pub total_mining_time: Duration,           // Kazılan blokların toplam kazma süresi
+    pub average_size: Option<f64>,             // Ortalama blok boyutu (byte)
+    pub difficulty_history: Vec<(usize, u32)>, // Zorluğun değiştiği yükseklikler ve yeni bits değeri
+}
+
+impl ChainStatistics {
+    // Kazılan blokların ortalama hash hızı
+    pub fn hash_rate(&self) -> Option<f64> {
+        (self.mined_here > 0).then(|| self.total_attempts as f64 / self.total_mining_time.as_secs_f64().max(f64::EPSILON))
+    }
+}
+
+impl Display for ChainStatistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Blocks: {} ({} mined in this session)", self.blocks, self.mined_here)?;
+        match (self.average_interval, self.interval_range) {
+            (Some(average), Some((min, max))) => writeln!(
+                f,
+                "Block interval: average {:.1}s, min {}s, max {}s (target {}s)",
+                average, min, max, TARGET_BLOCK_TIME
+            )?,
+            _ => writeln!(f, "Block interval: n/a (fewer than two blocks)")?,
+        }
+        if let Some(average) = self.average_size {
+            writeln!(f, "Average block size: {:.0} bytes", average)?;
+        }
+        match self.hash_rate() {
+            Some(hash_rate) => writeln!(
+                f,
+                "Mining: {} hash(es) in {:.2?}, {:.0} H/s, {:.2?} per block",
+                self.total_attempts,
+                self.total_mining_time,
+                hash_rate,
+                self.total_mining_time / self.mined_here as u32
+            )?,
+            None => writeln!(f, "Mining: no blocks mined in this session")?,
+        }
+        write!(f, "Difficulty history:")?;
+        for (height, bits) in &self.difficulty_history {
+            write!(f, "\n  from height {:>4}: bits {:#010x}", height, bits)?;
+        }
+        Ok(())
+    }
+}
+
+impl<C: Consensus> Blockchain<C> {
+    // Kazılan bloğun madencilik istatistiklerini kaydeder
+    pub fn record_mining(&mut self, hash: Hash, stats: MiningStats) {
+        self.mining_log.insert(hash, stats);
+    }
+
+    // Ana zincirdeki her bloğun ölçümleri
+    pub fn block_metrics(&self) -> Vec<BlockMetrics> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .map(|(height, block)| {
+                let previous = height.checked_sub(1).map(|previous| &self.blocks[previous]);
+                let stats = self.mining_log.get(&block.hash);
+                BlockMetrics {
+                    height,
+                    hash: to_hex(&block.hash),
+                    timestamp: block.timestamp,
+                    interval: previous.map(|previous| block.timestamp.saturating_sub(previous.timestamp)),
+                    bits: block.bits,
+                    work: self.consensus.block_weight(block),
+                    transactions: block.transactions.len(),
+                    size: block_size(block),
+                    attempts: stats.map(|stats| stats.attempts),
+                    mining_seconds: stats.map(|stats| stats.elapsed.as_secs_f64()),
+                    hash_rate: stats.map(MiningStats::hash_rate),
+                }
+            })
+            .collect()
+    }
+
+    // Zincirin geneline ait istatistikler
+    pub fn statistics(&self) -> ChainStatistics {
+        let metrics = self.block_metrics();
+        let intervals: Vec<u64> = metrics.iter().filter_map(|block| block.interval).collect();
+        let mined: Vec<&MiningStats> = self.blocks.iter().filter_map(|block| self.mining_log.get(&block.hash)).collect();
+        let mut difficulty_history: Vec<(usize, u32)> = Vec::new();
+        for block in &metrics {
+            if difficulty_history.last().is_none_or(|(_, bits)| *bits != block.bits) {
+                difficulty_history.push((block.height, block.bits));
+            }
+        }
+        ChainStatistics {
+            blocks: metrics.len(),
+            mined_here: mined.len(),
+            average_interval: average(intervals.iter().map(|&interval| interval as f64)),
+            interval_range: intervals.iter().min().zip(intervals.iter().max()).map(|(&min, &max)| (min, max)),
+            total_attempts: mined.iter().map(|stats| stats.attempts).sum(),
+            total_mining_time: mined.iter().map(|stats| stats.elapsed).sum(),
+            average_size: average(metrics.iter().map(|block| block.size as f64)),
+            difficulty_history,
+        }
+    }
+
+    // Blok başına ölçümleri CSV dosyasına yazar (başlık satırı alan adlarıdır; bilinmeyen değerler boş kalır)
+    pub fn export_metrics(&self, path: &Path) -> io::Result<()> {
+        let mut csv = csv::Writer::from_path(path)?;
+        for metrics in self.block_metrics() {
+            csv.serialize(metrics).map_err(io::Error::other)?;
+        }
+        csv.flush()
+    }
+}
+
+// Bloğun serileştirilmiş boyutu (havuzdaki işlem boyutlarıyla aynı ölçü)
+fn block_size(block: &Block) -> usize {
+    serde_json::to_vec(block).map_or(0, |bytes| bytes.len())
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), value| (sum + value, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::EASIEST_BITS;
+    use crate::miner::CancelToken;
+    use crate::tests::{chain_with, mine_block, temp_path};
+    use std::fs;
+
+    #[test]
+    fn mined_blocks_carry_their_mining_stats() {
+        let mut blockchain = Blockchain::with_difficulty(EASIEST_BITS);
+        mine_block(&mut blockchain, "peer", Vec::new()).unwrap(); // Kaydı olmayan blok (başka düğümden gelmiş gibi)
+        for _ in 0..2 {
+            let mut block = blockchain.block_template("miner").unwrap();
+            let stats = blockchain.seal(&mut block, &CancelToken::default()).unwrap();
+            let hash = block.hash;
+            blockchain.add_block(block).unwrap();
+            blockchain.record_mining(hash, stats);
+        }
+
+        let metrics = blockchain.block_metrics();
+        assert_eq!(metrics.len(), 3);
+        assert!(metrics[0].attempts.is_none() && metrics[0].interval.is_none());
+        assert!(metrics[1..].iter().all(|block| block.attempts.is_some_and(|attempts| attempts > 0)));
+        assert_eq!(metrics[1].interval, Some(blockchain.blocks[1].timestamp - blockchain.blocks[0].timestamp));
+        assert!(metrics.iter().all(|block| block.size > 0 && block.transactions == 1));
+
+        let statistics = blockchain.statistics();
+        assert_eq!((statistics.blocks, statistics.mined_here), (3, 2));
+        assert_eq!(statistics.total_attempts, metrics[1].attempts.unwrap() + metrics[2].attempts.unwrap());
+        assert_eq!(statistics.difficulty_history, vec![(0, EASIEST_BITS)]);
+        assert!(statistics.hash_rate().is_some());
+    }
+
+    #[test]
+    fn metrics_csv_has_a_row_per_block() {
+        let blockchain = chain_with(3, "miner");
+        let path = temp_path("metrics.csv");
+        blockchain.export_metrics(&path).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("height,hash,timestamp,interval,bits,work,"));
+        // Genesis'in aralığı ve kazma ölçümleri boş
+        assert!(lines[1].starts_with(&format!("0,{},{},,", to_hex(&blockchain.blocks[0].hash), blockchain.blocks[0].timestamp)));
+        fs::remove_file(&path).unwrap();
+    }
+}
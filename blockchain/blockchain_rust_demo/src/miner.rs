@@ -0,0 +1,237 @@
+// Çok iş parçacıklı (multi-threaded) madenci
+// Nonce uzayı iş parçacıkları arasında bölünür: i. iş parçacığı i, i + n, i + 2n ... nonce'larını dener
+// Biri geçerli hash bulduğu anda ortak bayrak set edilir ve diğerleri durur
+// Madencilik arka planda da çalıştırılabilir: ilerleme bir kanal (channel) üzerinden akar
+// ve iptal jetonu (CancelToken) ile her an durdurulabilir
+
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::consensus::{Consensus, SealError};
+use crate::difficulty::{bits_to_target, hash_meets_target};
+use crate::Block;
+
+// Madencilik ayarları
+#[derive(Clone)]
+pub struct Miner {
+    pub threads: usize,             // Paralel çalışan iş parçacığı sayısı
+    pub throttle: Option<Duration>, // Her denemeden sonra beklenecek süre (gösterim amaçlı yavaşlatma)
+}
+
+// Varsayılan: makinedeki çekirdek sayısı kadar iş parçacığı, bekleme yok
+impl Default for Miner {
+    fn default() -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Miner { threads, throttle: None }
+    }
+}
+
+// İlerleme raporlarının gönderilme sıklığı
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+// İşçilerin bitip bitmediğinin kontrol edilme sıklığı
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+// Madenciliği durdurmak için paylaşılan iptal jetonu (klonlar aynı bayrağı gösterir)
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+// Madencilik sürerken periyodik olarak gönderilen durum bilgisi
+pub struct MiningProgress {
+    pub attempts: u64,           // Şu ana kadar denenen hash sayısı
+    pub hash_rate: f64,          // Saniyedeki hash sayısı
+    pub best_leading_zeros: u32, // Bulunan en iyi hash'in baştaki sıfır bit sayısı
+}
+
+// Arka plandaki madencilikten gelen olaylar
+pub enum MiningEvent {
+    Progress(MiningProgress),
+    Finished(Box<Block>, MiningStats), // Kazılmış blok ve istatistikler
+    Cancelled,
+    Failed(SealError),                 // Blok bu düğümde mühürlenemiyor (örneğin sıra başka bir yetkilide)
+}
+
+// Bir bloğun madenciliği sonunda toplanan istatistikler
+#[derive(Clone, Copy)]
+pub struct MiningStats {
+    pub attempts: u64,     // Toplam denenen hash sayısı (tüm iş parçacıkları)
+    pub elapsed: Duration, // Geçen süre
+}
+
+impl MiningStats {
+    // Saniyedeki hash sayısı
+    pub fn hash_rate(&self) -> f64 {
+        self.attempts as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+impl Miner {
+    // Bloğu arka planda bir iş parçacığında uzlaşma motoruyla mühürler; ilerleme ve sonuç dönen kanaldan okunur
+    pub fn spawn<C: Consensus>(
+        &self,
+        consensus: Arc<C>,
+        mut block: Block,
+        cancel: CancelToken,
+    ) -> Receiver<MiningEvent> {
+        let (sender, receiver) = mpsc::channel();
+        let miner = self.clone();
+        thread::spawn(move || {
+            let progress_sender = sender.clone();
+            let mut report = move |progress| {
+                let _ = progress_sender.send(MiningEvent::Progress(progress)); // Alıcı gitmişse önemsenmez
+            };
+            let event = match consensus.seal(&mut block, &miner, &cancel, &mut report) {
+                Ok(stats) => MiningEvent::Finished(Box::new(block), stats),
+                Err(SealError::Cancelled) => MiningEvent::Cancelled,
+                Err(err) => MiningEvent::Failed(err),
+            };
+            let _ = sender.send(event);
+        });
+        receiver
+    }
+
+    // Blok hedefi sağlayan bir hash bulunana kadar nonce arar; bulunca nonce ve hash bloğa yazılır
+    // İlerleme report ile bildirilir; iptal edilirse None döner ve blok yarım kalır
+    // Bütün u32 nonce uzayı tükenirse zaman damgası bir artırılır (extra-nonce) ve arama baştan başlar
+    pub fn mine_with(
+        &self,
+        block: &mut Block,
+        cancel: &CancelToken,
+        mut report: impl FnMut(MiningProgress),
+    ) -> Option<MiningStats> {
+        let target = bits_to_target(block.bits);
+        let threads = self.threads.max(1);
+        let throttle = self.throttle;
+        let attempts = AtomicU64::new(0);
+        let best_leading_zeros = AtomicU32::new(0);
+        let started = Instant::now();
+
+        loop {
+            let found = AtomicBool::new(false);
+            let header = &*block;
+            // thread::scope, iş parçacıklarının bloğu ödünç alabilmesini sağlar (hepsi scope sonunda beklenir)
+            let winner = thread::scope(|scope| {
+                let workers: Vec<_> = (0..threads)
+                    .map(|worker| {
+                        let (found, attempts, best) = (&found, &attempts, &best_leading_zeros);
+                        scope.spawn(move || {
+                            let mut candidate = header.clone();
+                            let mut nonce = worker as u64;
+                            while nonce <= u32::MAX as u64 && !found.load(Ordering::Relaxed) && !cancel.is_cancelled() {
+                                candidate.nonce = nonce as u32;
+                                let hash = candidate.calculate_hash();
+                                attempts.fetch_add(1, Ordering::Relaxed);
+                                best.fetch_max(leading_zero_bits(&hash), Ordering::Relaxed);
+                                if hash_meets_target(&hash, &target) {
+                                    found.store(true, Ordering::Relaxed); // Diğer iş parçacıklarını durdur
+                                    return Some((candidate.nonce, hash));
+                                }
+                                if let Some(throttle) = throttle {
+                                    thread::sleep(throttle);
+                                }
+                                nonce += threads as u64;
+                            }
+                            None
+                        })
+                    })
+                    .collect();
+
+                // Çağıran iş parçacığı, işçiler çalışırken belirli aralıklarla ilerleme raporlar
+                let mut last_report = Instant::now();
+                while !workers.iter().all(|worker| worker.is_finished()) {
+                    thread::sleep(POLL_INTERVAL);
+                    if last_report.elapsed() >= PROGRESS_INTERVAL {
+                        last_report = Instant::now();
+                        let stats = MiningStats { attempts: attempts.load(Ordering::Relaxed), elapsed: started.elapsed() };
+                        report(MiningProgress {
+                            attempts: stats.attempts,
+                            hash_rate: stats.hash_rate(),
+                            best_leading_zeros: best_leading_zeros.load(Ordering::Relaxed),
+                        });
+                    }
+                }
+                workers
+                    .into_iter()
+                    .filter_map(|worker| worker.join().expect("Mining thread panicked"))
+                    .next()
+            });
+
+            if let Some((nonce, hash)) = winner {
+                block.nonce = nonce;
+                block.hash = hash;
+                break;
+            }
+            if cancel.is_cancelled() {
+                return None;
+            }
+            block.timestamp += 1; // Nonce uzayı tükendi: başlık değişsin diye zaman damgası ilerletilir
+        }
+
+        Some(MiningStats { attempts: attempts.into_inner(), elapsed: started.elapsed() })
+    }
+}
+
+// Hash'in başındaki sıfır bitlerinin sayısı (hedefe ne kadar yaklaşıldığının göstergesi)
+fn leading_zero_bits(hash: &[u8; 32]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::ProofOfWork;
+    use crate::difficulty::DEFAULT_BITS;
+    use crate::header::ZERO_HASH;
+
+    #[test]
+    fn parallel_miner_finds_valid_hash() {
+        let mut block = Block::new(0, ZERO_HASH, Vec::new(), DEFAULT_BITS, 0);
+        let stats = Miner { threads: 4, throttle: None }
+            .mine_with(&mut block, &CancelToken::default(), |_| {})
+            .unwrap();
+        assert!(ProofOfWork.verify_seal(&block).is_ok());
+        assert_eq!(block.hash, block.calculate_hash());
+        assert!(stats.attempts >= 1);
+    }
+
+    #[test]
+    fn cancelled_mining_reports_cancellation() {
+        // Hedef sıfır: hiçbir hash geçerli olamaz, madencilik ancak iptalle biter
+        let block = Block::new(0, ZERO_HASH, Vec::new(), 0, 0);
+        let cancel = CancelToken::default();
+        let events = Miner { threads: 2, throttle: None }.spawn(Arc::new(ProofOfWork), block, cancel.clone());
+        // İlk ilerleme raporunu bekleyip iptal et
+        assert!(matches!(events.recv().unwrap(), MiningEvent::Progress(_)));
+        cancel.cancel();
+        let last = events.iter().last().unwrap();
+        assert!(matches!(last, MiningEvent::Cancelled));
+    }
+
+    #[test]
+    fn counts_leading_zero_bits() {
+        let mut hash = [0u8; 32];
+        hash[2] = 0x10;
+        assert_eq!(leading_zero_bits(&hash), 19);
+        assert_eq!(leading_zero_bits(&[0u8; 32]), 256);
+    }
+}
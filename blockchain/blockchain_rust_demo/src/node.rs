@@ -0,0 +1,493 @@
+// Eşler arası (peer-to-peer) düğüm
+// Düğümler TCP üzerinden her satırı bir JSON mesajı olan sürümlü bir protokolle konuşur
+// Bağlantı el sıkışmayla (version / verack) başlar; sonra bloklar envanter (inventory) ile duyurulur,
+// eksik bloklar get_blocks ile istenir ve block mesajlarıyla gönderilir; işlemler de aynı şekilde yayılır
+
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::consensus::Consensus;
+use crate::fork::ChainEvent;
+use crate::mempool::MempoolError;
+use crate::merkle::Hash;
+use crate::storage::ChainStore;
+use crate::transaction::Transaction;
+use crate::{Block, BlockError, Blockchain};
+
+// Protokol sürümü; farklı sürümdeki eşlerle bağlantı kurulmaz
+// (2: blok hash'leri ikili başlığın çift SHA256'sı)
+pub const PROTOCOL_VERSION: u32 = 2;
+
+// Bir get_blocks isteğine en fazla kaç blokla cevap verilir (kalanı sonraki istekle alınır)
+const MAX_BLOCKS_PER_MESSAGE: usize = 500;
+
+// El sıkışma bu süre içinde tamamlanmazsa bağlantı kapatılır
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Tek bir mesaj satırının en büyük boyutu (byte); satır sonu göndermeyen eş belleği dolduramaz
+const MAX_MESSAGE_SIZE: u64 = 1024 * 1024;
+
+// Eşler arasında gönderilen mesajlar
+#[derive(Serialize, Deserialize)]
+pub enum Message {
+    Version { version: u32, height: u64, total_work: u128 }, // El sıkışmanın ilk mesajı: sürüm ve zincirin durumu
+    Verack,                                                  // Karşı tarafın sürümü kabul edildi
+    Inventory {
+        #[serde(with = "crate::header::hex_hashes")]
+        blocks: Vec<Hash>, // Gönderenin sahip olduğu blokların hash'leri
+    },
+    GetBlocks {
+        #[serde(with = "crate::header::hex_hashes")]
+        locator: Vec<Hash>, // Locator'daki ilk ortak bloktan sonraki blokları iste
+    },
+    Block { block: Box<Block> },                             // Tek bir blok
+    Transaction { transaction: Transaction },                // Henüz bloğa girmemiş bir işlem
+}
+
+// Ağ tarafında oluşabilecek hatalar
+#[derive(Debug)]
+pub enum NodeError {
+    Io(io::Error),                                 // Soket hatası
+    Disconnected,                                  // Karşı taraf bağlantıyı kapattı
+    Malformed(String),                             // Mesaj çözülemedi
+    VersionMismatch { ours: u32, theirs: u32 },    // Protokol sürümleri uyuşmuyor
+    UnexpectedMessage,                             // El sıkışma sırasına uymayan mesaj
+}
+
+impl Display for NodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeError::Io(err) => write!(f, "I/O error: {}", err),
+            NodeError::Disconnected => write!(f, "peer disconnected"),
+            NodeError::Malformed(reason) => write!(f, "malformed message: {}", reason),
+            NodeError::VersionMismatch { ours, theirs } => {
+                write!(f, "protocol version mismatch (ours {}, theirs {})", ours, theirs)
+            }
+            NodeError::UnexpectedMessage => write!(f, "unexpected message"),
+        }
+    }
+}
+
+impl std::error::Error for NodeError {}
+
+impl From<io::Error> for NodeError {
+    fn from(err: io::Error) -> Self {
+        NodeError::Io(err)
+    }
+}
+
+// El sıkışması tamamlanmış bir eş
+struct Peer {
+    address: SocketAddr,
+    writer: Mutex<TcpStream>, // Birden çok iş parçacığı aynı anda yazabileceği için kilitlenir
+    total_work: u128,         // El sıkışmada bildirilen zincir işi
+}
+
+impl Peer {
+    fn send(&self, message: &Message) -> Result<(), NodeError> {
+        let mut writer = self.writer.lock().unwrap();
+        write_message(&mut writer, message)
+    }
+}
+
+// Zinciri eşlerle paylaşan düğüm (klonlar aynı zinciri ve eş listesini gösterir)
+pub struct Node<C: Consensus> {
+    chain: Arc<Mutex<Blockchain<C>>>,
+    store: ChainStore,                  // Ağdan gelen bloklar da diske yazılır
+    peers: Arc<Mutex<Vec<Arc<Peer>>>>,
+    address: SocketAddr,                // Dinlenen adres
+}
+
+// derive, motorun da Clone olmasını şart koşacağı için elle yazılır
+impl<C: Consensus> Clone for Node<C> {
+    fn clone(&self) -> Self {
+        Node {
+            chain: Arc::clone(&self.chain),
+            store: self.store.clone(),
+            peers: Arc::clone(&self.peers),
+            address: self.address,
+        }
+    }
+}
+
+impl<C: Consensus> Node<C> {
+    // Yerel makinede (127.0.0.1) verilen portu dinlemeye başlar; gelen her bağlantı ayrı bir iş parçacığında karşılanır
+    // (Port 0 verilirse işletim sistemi boş bir port seçer)
+    pub fn start(chain: Arc<Mutex<Blockchain<C>>>, store: ChainStore, port: u16) -> io::Result<Node<C>> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let node = Node {
+            chain,
+            store,
+            peers: Arc::default(),
+            address: listener.local_addr()?,
+        };
+        let acceptor = node.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let node = acceptor.clone();
+                thread::spawn(move || {
+                    let address = stream.peer_addr().ok();
+                    if let Err(err) = node.accept(stream) {
+                        println!("[node] handshake with {:?} failed: {}", address, err);
+                    }
+                });
+            }
+        });
+        Ok(node)
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn peer_count(&self) -> usize {
+        self.peers.lock().unwrap().len()
+    }
+
+    // Başlangıçta verilen eşlere bağlanır ve zinciri en çok işe sahip eşten ister
+    pub fn sync(&self, addresses: &[String]) {
+        let mut best: Option<Arc<Peer>> = None;
+        for address in addresses {
+            match self.connect(address) {
+                Ok(peer) => {
+                    println!("[node] connected to {} (total work {})", peer.address, peer.total_work);
+                    if best.as_ref().is_none_or(|best| peer.total_work > best.total_work) {
+                        best = Some(peer);
+                    }
+                }
+                Err(err) => println!("[node] could not connect to {}: {}", address, err),
+            }
+        }
+        if let Some(peer) = best {
+            self.request_blocks_if_behind(&peer);
+        }
+    }
+
+    // Yeni bir blok (madencilikle ya da yeniden düzenlemeyle) zincirin ucuna geldiğinde eşlere duyurulur
+    pub fn announce_block(&self, hash: &Hash) {
+        self.broadcast(&Message::Inventory { blocks: vec![*hash] }, None);
+    }
+
+    // Yerelde oluşturulan işlem eşlere gönderilir
+    pub fn broadcast_transaction(&self, transaction: &Transaction) {
+        self.broadcast(&Message::Transaction { transaction: transaction.clone() }, None);
+    }
+
+    // Giden bağlantı: önce kendi sürümümüzü gönderir, karşı tarafınkini bekleriz
+    fn connect(&self, address: &str) -> Result<Arc<Peer>, NodeError> {
+        let stream = TcpStream::connect(address)?;
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+        write_message(&mut writer, &self.version())?;
+        let total_work = expect_version(&mut reader)?;
+        write_message(&mut writer, &Message::Verack)?;
+        expect_verack(&mut reader)?;
+        self.register(writer, reader, total_work).map_err(NodeError::from)
+    }
+
+    // Gelen bağlantı: karşı tarafın sürümünü bekler, kendi sürümümüzle cevap veririz
+    // Eşin zinciri bizimkinden ağırsa bloklar hemen istenir
+    fn accept(&self, stream: TcpStream) -> Result<(), NodeError> {
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+        let total_work = expect_version(&mut reader)?;
+        write_message(&mut writer, &self.version())?;
+        write_message(&mut writer, &Message::Verack)?;
+        expect_verack(&mut reader)?;
+        let peer = self.register(writer, reader, total_work)?;
+        println!("[node] peer {} connected (total work {})", peer.address, peer.total_work);
+        self.request_blocks_if_behind(&peer);
+        Ok(())
+    }
+
+    fn version(&self) -> Message {
+        let chain = self.chain.lock().unwrap();
+        Message::Version {
+            version: PROTOCOL_VERSION,
+            height: chain.blocks.len() as u64,
+            total_work: chain.total_work(),
+        }
+    }
+
+    // El sıkışması biten eşi listeye ekler ve mesajlarını okuyan iş parçacığını başlatır
+    fn register(&self, stream: TcpStream, reader: BufReader<TcpStream>, total_work: u128) -> io::Result<Arc<Peer>> {
+        stream.set_read_timeout(None)?;
+        let peer = Arc::new(Peer { address: stream.peer_addr()?, writer: Mutex::new(stream), total_work });
+        self.peers.lock().unwrap().push(Arc::clone(&peer));
+        let node = self.clone();
+        let serving = Arc::clone(&peer);
+        thread::spawn(move || node.serve(serving, reader));
+        Ok(peer)
+    }
+
+    // Eşin mesajlarını bağlantı kapanana kadar işler
+    fn serve(&self, peer: Arc<Peer>, mut reader: BufReader<TcpStream>) {
+        let result = loop {
+            if let Err(err) = read_message(&mut reader).and_then(|message| self.handle(&peer, message)) {
+                break err;
+            }
+        };
+        self.peers.lock().unwrap().retain(|other| !Arc::ptr_eq(other, &peer));
+        match result {
+            NodeError::Disconnected => println!("[node] peer {} disconnected", peer.address),
+            err => println!("[node] dropped peer {}: {}", peer.address, err),
+        }
+    }
+
+    fn handle(&self, peer: &Arc<Peer>, message: Message) -> Result<(), NodeError> {
+        match message {
+            // El sıkışma mesajları bağlantı başında bir kez gelir
+            Message::Version { .. } | Message::Verack => Err(NodeError::UnexpectedMessage),
+            Message::Inventory { blocks } => {
+                let locator = {
+                    let chain = self.chain.lock().unwrap();
+                    if blocks.iter().all(|hash| chain.contains(hash)) {
+                        return Ok(());
+                    }
+                    locator(&chain)
+                };
+                peer.send(&Message::GetBlocks { locator })
+            }
+            Message::GetBlocks { locator } => {
+                let (blocks, tip) = {
+                    let chain = self.chain.lock().unwrap();
+                    // Ortak blok bulunamazsa (ör. farklı genesis) zincir baştan gönderilir
+                    let start = locator
+                        .iter()
+                        .find_map(|hash| chain.main_height_of(hash))
+                        .map_or(0, |height| height + 1);
+                    let end = chain.blocks.len().min(start + MAX_BLOCKS_PER_MESSAGE);
+                    let tip = chain.blocks.last().filter(|_| end < chain.blocks.len()).map(|tip| tip.hash);
+                    (chain.blocks[start.min(end)..end].to_vec(), tip)
+                };
+                for block in blocks {
+                    peer.send(&Message::Block { block: Box::new(block) })?;
+                }
+                // Gönderilmeyen bloklar kaldıysa uç duyurulur; eş kalanını yeni bir istekle alır
+                if let Some(tip) = tip {
+                    peer.send(&Message::Inventory { blocks: vec![tip] })?;
+                }
+                Ok(())
+            }
+            Message::Block { block } => self.handle_block(peer, *block),
+            Message::Transaction { transaction } => {
+                self.handle_transaction(peer, transaction);
+                Ok(())
+            }
+        }
+    }
+
+    // Gelen bloğu zincire işler, diske yazar; ana zincir değiştiyse diğer eşlere duyurur
+    fn handle_block(&self, peer: &Arc<Peer>, block: Block) -> Result<(), NodeError> {
+        let (events, tip, locator) = {
+            let mut chain = self.chain.lock().unwrap();
+            let events = match chain.receive_block(block) {
+                Ok(events) => events,
+                Err(BlockError::Duplicate) => return Ok(()),
+                Err(err) => {
+                    println!("[node] rejected block from {}: {}", peer.address, err);
+                    return Ok(());
+                }
+            };
+            if let Err(err) = self.store.save(&chain, &events) {
+                eprintln!("[node] failed to save chain: {}", err);
+            }
+            let tip = chain.blocks.last().map(|tip| tip.hash);
+            (events, tip, locator(&chain))
+        };
+
+        for event in &events {
+            println!("[node] {}", event);
+        }
+        // Yetim blok: aradaki eksik bloklar aynı eşten istenir
+        if events.iter().any(|event| matches!(event, ChainEvent::Orphan { .. })) {
+            peer.send(&Message::GetBlocks { locator })?;
+        }
+        let changed = events
+            .iter()
+            .any(|event| matches!(event, ChainEvent::Extended { .. } | ChainEvent::Reorganized { .. }));
+        if let Some(tip) = tip.filter(|_| changed) {
+            self.broadcast(&Message::Inventory { blocks: vec![tip] }, Some(peer));
+        }
+        Ok(())
+    }
+
+    // Gelen işlem havuza kabul edilirse diğer eşlere iletilir
+    fn handle_transaction(&self, peer: &Arc<Peer>, transaction: Transaction) {
+        match self.chain.lock().unwrap().submit_transaction(transaction.clone()) {
+            Ok(_) => {}
+            Err(MempoolError::Duplicate) => return, // Daha önce alınmış; tekrar yayılmaz
+            Err(err) => {
+                println!("[node] rejected transaction from {}: {}", peer.address, err);
+                return;
+            }
+        }
+        println!("[node] received transaction {} from {}", transaction.txid(), peer.address);
+        self.broadcast(&Message::Transaction { transaction }, Some(peer));
+    }
+
+    // Eşin zinciri bizimkinden ağırsa eksik bloklar ondan istenir
+    fn request_blocks_if_behind(&self, peer: &Peer) {
+        let locator = {
+            let chain = self.chain.lock().unwrap();
+            if peer.total_work <= chain.total_work() {
+                return;
+            }
+            locator(&chain)
+        };
+        println!("[node] syncing from {}", peer.address);
+        if let Err(err) = peer.send(&Message::GetBlocks { locator }) {
+            println!("[node] sync request to {} failed: {}", peer.address, err);
+        }
+    }
+
+    // Mesajı (varsa kaynağı hariç) tüm eşlere gönderir; gönderilemeyen eşler okuyucu kapanınca listeden düşer
+    fn broadcast(&self, message: &Message, except: Option<&Arc<Peer>>) {
+        let peers = self.peers.lock().unwrap().clone();
+        for peer in peers.iter().filter(|peer| except.is_none_or(|except| !Arc::ptr_eq(peer, except))) {
+            if let Err(err) = peer.send(message) {
+                println!("[node] failed to send to {}: {}", peer.address, err);
+            }
+        }
+    }
+}
+
+// Zincirin ucundan genesis'e doğru seyrekleşen hash listesi (uç, uç-1, ... sonra 2, 4, 8 adım geri)
+// Karşı taraf tanıdığı ilk hash'ten sonrasını gönderir; böylece çatallanmada ortak ata az mesajla bulunur
+fn locator<C: Consensus>(chain: &Blockchain<C>) -> Vec<Hash> {
+    let mut locator = Vec::new();
+    let mut step = 1;
+    let mut height = chain.blocks.len().checked_sub(1);
+    while let Some(current) = height {
+        locator.push(chain.blocks[current].hash);
+        if locator.len() >= 10 {
+            step *= 2;
+        }
+        height = if current == 0 { None } else { Some(current.saturating_sub(step)) };
+    }
+    locator
+}
+
+// Mesajı tek satır JSON olarak yazar
+fn write_message(stream: &mut TcpStream, message: &Message) -> Result<(), NodeError> {
+    let mut line = serde_json::to_string(message).map_err(|err| NodeError::Malformed(err.to_string()))?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+// Sonraki satırı okuyup mesaja çevirir
+// Satır MAX_MESSAGE_SIZE byte içinde bitmezse mesaj bozuk sayılır
+fn read_message(reader: &mut BufReader<TcpStream>) -> Result<Message, NodeError> {
+    let mut line = String::new();
+    if reader.by_ref().take(MAX_MESSAGE_SIZE + 1).read_line(&mut line)? == 0 {
+        return Err(NodeError::Disconnected);
+    }
+    if line.len() as u64 > MAX_MESSAGE_SIZE {
+        return Err(NodeError::Malformed(format!("message exceeds {} bytes", MAX_MESSAGE_SIZE)));
+    }
+    serde_json::from_str(&line).map_err(|err| NodeError::Malformed(err.to_string()))
+}
+
+// El sıkışmada sürüm mesajını bekler; eşin zincir işini döndürür
+fn expect_version(reader: &mut BufReader<TcpStream>) -> Result<u128, NodeError> {
+    match read_message(reader)? {
+        Message::Version { version, total_work, .. } if version == PROTOCOL_VERSION => Ok(total_work),
+        Message::Version { version, .. } => Err(NodeError::VersionMismatch { ours: PROTOCOL_VERSION, theirs: version }),
+        _ => Err(NodeError::UnexpectedMessage),
+    }
+}
+
+fn expect_verack(reader: &mut BufReader<TcpStream>) -> Result<(), NodeError> {
+    match read_message(reader)? {
+        Message::Verack => Ok(()),
+        _ => Err(NodeError::UnexpectedMessage),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::ProofOfWork;
+    use crate::difficulty::EASIEST_BITS;
+    use crate::tests::{chain_with, mine_block, temp_store};
+    use std::time::Instant;
+
+    // Koşul sağlanana kadar (en fazla birkaç saniye) bekler
+    fn wait_until(condition: impl Fn() -> bool) -> bool {
+        let started = Instant::now();
+        while started.elapsed() < Duration::from_secs(5) {
+            if condition() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    #[test]
+    fn locator_thins_out_towards_genesis() {
+        let chain = chain_with(30, "miner");
+        let locator = locator(&chain);
+        assert_eq!(locator.first(), Some(&chain.blocks[29].hash));
+        assert_eq!(locator.last(), Some(&chain.blocks[0].hash));
+        assert!(locator.len() < 20);
+    }
+
+    #[test]
+    fn nodes_sync_on_startup_and_gossip_new_blocks() {
+        let (first_store, second_store) = (temp_store("node-first"), temp_store("node-second"));
+        let first_chain = Arc::new(Mutex::new(chain_with(3, "miner")));
+        let first = Node::start(Arc::clone(&first_chain), first_store.clone(), 0).unwrap();
+
+        // Boş düğüm bağlandığında zinciri ağır olan eşten çeker ve diske yazar
+        let second_chain = Arc::new(Mutex::new(chain_with(0, "miner")));
+        let second = Node::start(Arc::clone(&second_chain), second_store.clone(), 0).unwrap();
+        second.sync(&[format!("127.0.0.1:{}", first.address().port())]);
+        assert!(wait_until(|| second_chain.lock().unwrap().blocks.len() == 3));
+        assert_eq!(second_store.load(Arc::new(ProofOfWork), EASIEST_BITS).unwrap().blocks.len(), 3);
+
+        // İkinci düğümde kazılan blok duyurulur ve ilk düğüm tarafından alınır
+        let hash = {
+            let mut chain = second_chain.lock().unwrap();
+            mine_block(&mut chain, "second", Vec::new()).unwrap();
+            chain.blocks.last().unwrap().hash
+        };
+        second.announce_block(&hash);
+        assert!(wait_until(|| first_chain.lock().unwrap().blocks.last().unwrap().hash == hash));
+
+        let _ = std::fs::remove_file(first_store.path());
+        let _ = std::fs::remove_file(second_store.path());
+    }
+
+    #[test]
+    fn peer_with_other_protocol_version_is_refused() {
+        let node = Node::start(Arc::new(Mutex::new(chain_with(0, "miner"))), temp_store("node-version"), 0).unwrap();
+        let mut stream = TcpStream::connect(("127.0.0.1", node.address().port())).unwrap();
+        let version = Message::Version { version: PROTOCOL_VERSION + 1, height: 0, total_work: 0 };
+        write_message(&mut stream, &version).unwrap();
+        // Düğüm cevap vermeden bağlantıyı kapatır
+        let mut reader = BufReader::new(stream);
+        assert!(matches!(read_message(&mut reader), Err(NodeError::Disconnected)));
+        assert_eq!(node.peer_count(), 0);
+    }
+
+    #[test]
+    fn endless_message_line_is_refused() {
+        let node = Node::start(Arc::new(Mutex::new(chain_with(0, "miner"))), temp_store("node-endless"), 0).unwrap();
+        let mut stream = TcpStream::connect(("127.0.0.1", node.address().port())).unwrap();
+        // Satır sonu olmayan, sınırdan bir byte uzun mesaj
+        stream.write_all(&vec![b'x'; MAX_MESSAGE_SIZE as usize + 1]).unwrap();
+        let mut reader = BufReader::new(stream);
+        assert!(matches!(read_message(&mut reader), Err(NodeError::Disconnected | NodeError::Io(_))));
+        assert_eq!(node.peer_count(), 0);
+    }
+}
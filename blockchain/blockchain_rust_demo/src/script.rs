@@ -0,0 +1,490 @@
+// Bitcoin Script benzeri, yığın (stack) tabanlı küçük bir betik dili
+// Bir çıktı kilitleme betiği (locking script) taşıyabilir; onu harcayan girdi de bir açma betiği (unlocking script) verir
+// Doğrulamada önce açma betiği, sonra aynı yığın üzerinde kilitleme betiği çalıştırılır; betik hatasız biter ve
+// yığının tepesindeki değer "doğru" ise harcama geçerlidir
+//
+// İşlem kodları (opcode) Bitcoin'deki byte değerlerini kullanır:
+//   <veri>                 0x00-0x4b   Veriyi yığına koyar (0x00 boş veri, yani sıfır)
+//   OP_DROP                0x75        Tepedeki değeri atar
+//   OP_DUP                 0x76        Tepedeki değeri çoğaltır
+//   OP_EQUAL               0x87        İki değeri karşılaştırır, sonucu yığına koyar
+//   OP_EQUALVERIFY         0x88        İki değer eşit değilse betik başarısız olur
+//   OP_HASH160             0xa9        Tepedeki değeri RIPEMD160(SHA256(x)) ile değiştirir
+//   OP_CHECKSIG            0xac        <imza> <açık anahtar> imzayı işlemin imzalanan içeriğine karşı doğrular
+//   OP_CHECKSIGVERIFY      0xad        İmza geçersizse betik başarısız olur
+//   OP_CHECKMULTISIG       0xae        <imza...> m <anahtar...> n: m imza n anahtardan sırayla eşleşmeli
+//   OP_CHECKLOCKTIMEVERIFY 0xb1        Blok yüksekliği tepedeki değerden küçükse betik başarısız olur (zaman kilidi)
+//
+// Sayılar en kısa big-endian byte dizisi olarak tutulur (en fazla 8 byte); sıfır boş dizidir
+// Sonsuz ya da aşırı pahalı betiklere karşı adım ve yığın sınırları vardır
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use ed25519_dalek::{Signature, VerifyingKey};
+use ripemd::Ripemd160;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+use crate::{from_hex, to_hex};
+
+// Açma ve kilitleme betiklerinde toplam çalıştırılabilecek işlem sayısı
+pub const MAX_SCRIPT_STEPS: usize = 201;
+
+// Yığında aynı anda bulunabilecek en fazla değer sayısı
+pub const MAX_STACK_SIZE: usize = 1_000;
+
+// OP_CHECKMULTISIG'in kabul ettiği en fazla anahtar sayısı
+pub const MAX_MULTISIG_KEYS: usize = 20;
+
+// Tek bir işlemle yığına konabilecek en uzun veri (tek byte uzunluk önekine sığar)
+const MAX_PUSH_SIZE: usize = 0x4b;
+
+const OP_DROP: u8 = 0x75;
+const OP_DUP: u8 = 0x76;
+const OP_EQUAL: u8 = 0x87;
+const OP_EQUALVERIFY: u8 = 0x88;
+const OP_HASH160: u8 = 0xa9;
+const OP_CHECKSIG: u8 = 0xac;
+const OP_CHECKSIGVERIFY: u8 = 0xad;
+const OP_CHECKMULTISIG: u8 = 0xae;
+const OP_CHECKLOCKTIMEVERIFY: u8 = 0xb1;
+
+// Betiğin bir adımı
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Op {
+    Push(Vec<u8>),
+    Drop,
+    Dup,
+    Equal,
+    EqualVerify,
+    Hash160,
+    CheckSig,
+    CheckSigVerify,
+    CheckMultiSig,
+    CheckLockTimeVerify,
+}
+
+impl Op {
+    // Assembly gösterimindeki adı ve byte değeri (Push hariç)
+    const NAMED: [(Op, &'static str, u8); 9] = [
+        (Op::Drop, "OP_DROP", OP_DROP),
+        (Op::Dup, "OP_DUP", OP_DUP),
+        (Op::Equal, "OP_EQUAL", OP_EQUAL),
+        (Op::EqualVerify, "OP_EQUALVERIFY", OP_EQUALVERIFY),
+        (Op::Hash160, "OP_HASH160", OP_HASH160),
+        (Op::CheckSig, "OP_CHECKSIG", OP_CHECKSIG),
+        (Op::CheckSigVerify, "OP_CHECKSIGVERIFY", OP_CHECKSIGVERIFY),
+        (Op::CheckMultiSig, "OP_CHECKMULTISIG", OP_CHECKMULTISIG),
+        (Op::CheckLockTimeVerify, "OP_CHECKLOCKTIMEVERIFY", OP_CHECKLOCKTIMEVERIFY),
+    ];
+
+    // Sayıyı yığına koyan işlem
+    pub fn number(value: u64) -> Op {
+        let bytes = value.to_be_bytes();
+        let start = bytes.iter().position(|&byte| byte != 0).unwrap_or(bytes.len());
+        Op::Push(bytes[start..].to_vec())
+    }
+}
+
+// Betik çalıştırılamadığında ya da harcamayı onaylamadığında dönen hatalar
+#[derive(Debug, PartialEq, Eq)]
+pub enum ScriptError {
+    Malformed,                            // Byte dizisi geçerli bir betik değil
+    NotPushOnly,                          // Açma betiği veri koymak dışında işlem içeriyor
+    TooManySteps,                         // Adım sınırı aşıldı
+    StackOverflow,                        // Yığın sınırı aşıldı
+    StackUnderflow,                       // İşlem için yığında yeterli değer yok
+    PushTooLarge,                         // Yığına konan veri MAX_PUSH_SIZE byte'tan uzun (tek byte uzunluk önekine sığmaz)
+    InvalidNumber,                        // Sayı beklenen yerde 8 byte'tan uzun ya da en kısa biçimde olmayan veri
+    InvalidMultisig,                      // Anahtar ya da imza sayısı geçersiz
+    VerifyFailed,                         // OP_*VERIFY koşulu sağlanmadı
+    Locked { until: u64, height: u64 },   // Zaman kilidi henüz açılmadı
+    FalseResult,                          // Betik bitti ama yığının tepesi "yanlış"
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptError::Malformed => write!(f, "malformed script"),
+            ScriptError::NotPushOnly => write!(f, "unlocking script may only push data"),
+            ScriptError::TooManySteps => write!(f, "script exceeds {} steps", MAX_SCRIPT_STEPS),
+            ScriptError::StackOverflow => write!(f, "script stack exceeds {} items", MAX_STACK_SIZE),
+            ScriptError::StackUnderflow => write!(f, "script stack underflow"),
+            ScriptError::PushTooLarge => write!(f, "data push exceeds {} bytes", MAX_PUSH_SIZE),
+            ScriptError::InvalidNumber => write!(f, "invalid number on the script stack"),
+            ScriptError::InvalidMultisig => write!(f, "invalid multisig key or signature count"),
+            ScriptError::VerifyFailed => write!(f, "script verification failed"),
+            ScriptError::Locked { until, height } => {
+                write!(f, "output is time-locked until height {} (block height is {})", until, height)
+            }
+            ScriptError::FalseResult => write!(f, "script finished with a false result"),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+// Betiğin çalıştığı işlem bağlamı
+pub struct ScriptContext<'a> {
+    pub message: &'a [u8], // İşlemin imzalanan içeriği (OP_CHECKSIG imzaları buna karşı doğrular)
+    pub height: u64,       // Harcamanın gireceği bloğun yüksekliği (OP_CHECKLOCKTIMEVERIFY için)
+}
+
+// İşlem dizisi; JSON'da ikili kodlamasının hex hali olarak yazılır
+// Alan gizlidir: betik sadece veri boyutlarını denetleyen kurucularla oluşur, böylece her betik kodlanabilir
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Script(Vec<Op>);
+
+impl Script {
+    // Verilen işlemlerden betik oluşturur; MAX_PUSH_SIZE byte'tan uzun veri reddedilir
+    pub fn new(ops: Vec<Op>) -> Result<Script, ScriptError> {
+        if ops.iter().any(|op| matches!(op, Op::Push(data) if data.len() > MAX_PUSH_SIZE)) {
+            return Err(ScriptError::PushTooLarge);
+        }
+        Ok(Script(ops))
+    }
+
+    // m-of-n çoklu imza: anahtarlardan en az m tanesinin (listedeki sırayla) imzası gerekir
+    pub fn multisig(required: usize, keys: &[VerifyingKey]) -> Script {
+        let mut ops = vec![Op::number(required as u64)];
+        ops.extend(keys.iter().map(|key| Op::Push(key.as_bytes().to_vec())));
+        ops.push(Op::number(keys.len() as u64));
+        ops.push(Op::CheckMultiSig);
+        Script(ops)
+    }
+
+    // Zaman kilitli ödeme: verilen yükseklikten önce harcanamaz, sonra anahtarın imzasıyla harcanır
+    pub fn timelock(height: u64, key: &VerifyingKey) -> Script {
+        Script(vec![
+            Op::number(height),
+            Op::CheckLockTimeVerify,
+            Op::Drop,
+            Op::Push(key.as_bytes().to_vec()),
+            Op::CheckSig,
+        ])
+    }
+
+    // Sadece verilen değerleri sırayla yığına koyan betik (açma betikleri bu biçimdedir)
+    pub fn pushes(items: Vec<Vec<u8>>) -> Result<Script, ScriptError> {
+        Script::new(items.into_iter().map(Op::Push).collect())
+    }
+
+    // İkili kodlama: her işlem kendi byte'ıyla, veri ise önündeki uzunluk byte'ıyla yazılır
+    // (kurucular veri boyutunu denetlediği için uzunluk her zaman tek byte'a sığar)
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for op in &self.0 {
+            match op {
+                Op::Push(data) => {
+                    bytes.push(data.len() as u8);
+                    bytes.extend_from_slice(data);
+                }
+                op => bytes.push(Op::NAMED.iter().find(|(named, _, _)| named == op).map_or(0, |(_, _, code)| *code)),
+            }
+        }
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Script, ScriptError> {
+        let mut ops = Vec::new();
+        let mut rest = bytes;
+        while let Some((&code, tail)) = rest.split_first() {
+            if code as usize <= MAX_PUSH_SIZE {
+                let data = tail.get(..code as usize).ok_or(ScriptError::Malformed)?;
+                ops.push(Op::Push(data.to_vec()));
+                rest = &tail[code as usize..];
+            } else {
+                let (op, _, _) = Op::NAMED.iter().find(|(_, _, named)| *named == code).ok_or(ScriptError::Malformed)?;
+                ops.push(op.clone());
+                rest = tail;
+            }
+        }
+        Ok(Script(ops))
+    }
+
+    fn is_push_only(&self) -> bool {
+        self.0.iter().all(|op| matches!(op, Op::Push(_)))
+    }
+}
+
+// Okunur gösterim: işlemler adlarıyla, en kısa biçimdeki küçük sayılar ondalık, diğer veriler hex olarak yazılır
+// (örnek: "2 <anahtar> <anahtar> 2 OP_CHECKMULTISIG")
+impl Display for Script {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let words: Vec<String> = self
+            .0
+            .iter()
+            .map(|op| match op {
+                Op::Push(data) => match parse_number(data) {
+                    Ok(value) => value.to_string(),
+                    Err(_) => format!("0x{}", to_hex(data)),
+                },
+                op => Op::NAMED.iter().find(|(named, _, _)| named == op).map_or("?", |(_, name, _)| name).to_string(),
+            })
+            .collect();
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+impl FromStr for Script {
+    type Err = ScriptError;
+
+    fn from_str(assembly: &str) -> Result<Script, ScriptError> {
+        let ops = assembly
+            .split_whitespace()
+            .map(|word| {
+                if let Some(hex) = word.strip_prefix("0x") {
+                    return from_hex(hex).filter(|data| data.len() <= MAX_PUSH_SIZE).map(Op::Push);
+                }
+                if let Ok(value) = word.parse::<u64>() {
+                    return Some(Op::number(value));
+                }
+                Op::NAMED.iter().find(|(_, name, _)| name.eq_ignore_ascii_case(word)).map(|(op, _, _)| op.clone())
+            })
+            .collect::<Option<Vec<Op>>>()
+            .ok_or(ScriptError::Malformed)?;
+        Ok(Script(ops))
+    }
+}
+
+impl Serialize for Script {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(&self.encode()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Script {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Script, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        let bytes = from_hex(&hex).ok_or_else(|| de::Error::custom(format!("invalid script hex: {}", hex)))?;
+        Script::decode(&bytes).map_err(de::Error::custom)
+    }
+}
+
+// Betiğin ya da açık anahtarın HASH160'ı: RIPEMD160(SHA256(x))
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+// Açma betiğini ve ardından kilitleme betiğini aynı yığın üzerinde çalıştırır
+pub fn execute(unlocking: &Script, locking: &Script, context: &ScriptContext) -> Result<(), ScriptError> {
+    if !unlocking.is_push_only() {
+        return Err(ScriptError::NotPushOnly);
+    }
+    let mut machine = Machine { stack: Vec::new(), steps: 0, context };
+    for op in unlocking.0.iter().chain(&locking.0) {
+        machine.step(op)?;
+    }
+    match machine.stack.last() {
+        Some(top) if is_true(top) => Ok(()),
+        _ => Err(ScriptError::FalseResult),
+    }
+}
+
+// Yorumlayıcının durumu
+struct Machine<'a> {
+    stack: Vec<Vec<u8>>,
+    steps: usize,
+    context: &'a ScriptContext<'a>,
+}
+
+impl Machine<'_> {
+    fn step(&mut self, op: &Op) -> Result<(), ScriptError> {
+        self.steps += 1;
+        if self.steps > MAX_SCRIPT_STEPS {
+            return Err(ScriptError::TooManySteps);
+        }
+        match op {
+            Op::Push(data) => self.push(data.clone())?,
+            Op::Drop => {
+                self.pop()?;
+            }
+            Op::Dup => {
+                let top = self.stack.last().ok_or(ScriptError::StackUnderflow)?.clone();
+                self.push(top)?;
+            }
+            Op::Equal | Op::EqualVerify => {
+                let (b, a) = (self.pop()?, self.pop()?);
+                self.conclude(a == b, matches!(op, Op::EqualVerify))?;
+            }
+            Op::Hash160 => {
+                let top = self.pop()?;
+                self.push(hash160(&top).to_vec())?;
+            }
+            Op::CheckSig | Op::CheckSigVerify => {
+                let (key, signature) = (self.pop()?, self.pop()?);
+                let valid = check_signature(&signature, &key, self.context.message);
+                self.conclude(valid, matches!(op, Op::CheckSigVerify))?;
+            }
+            Op::CheckMultiSig => {
+                let key_count = self.pop_number()? as usize;
+                if key_count > MAX_MULTISIG_KEYS {
+                    return Err(ScriptError::InvalidMultisig);
+                }
+                let keys = self.pop_many(key_count)?;
+                let required = self.pop_number()? as usize;
+                if required > key_count {
+                    return Err(ScriptError::InvalidMultisig);
+                }
+                let signatures = self.pop_many(required)?;
+                // İmzalar anahtarlarla aynı sırada olmalı: her imza, kendinden sonraki ilk uyan anahtarla eşleşir
+                let mut remaining = keys.iter();
+                let valid = signatures.iter().all(|signature| {
+                    remaining.any(|key| check_signature(signature, key, self.context.message))
+                });
+                self.conclude(valid, false)?;
+            }
+            Op::CheckLockTimeVerify => {
+                let top = self.stack.last().ok_or(ScriptError::StackUnderflow)?;
+                let until = parse_number(top)?;
+                if self.context.height < until {
+                    return Err(ScriptError::Locked { until, height: self.context.height });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, value: Vec<u8>) -> Result<(), ScriptError> {
+        if self.stack.len() >= MAX_STACK_SIZE {
+            return Err(ScriptError::StackOverflow);
+        }
+        self.stack.push(value);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<Vec<u8>, ScriptError> {
+        self.stack.pop().ok_or(ScriptError::StackUnderflow)
+    }
+
+    fn pop_number(&mut self) -> Result<u64, ScriptError> {
+        parse_number(&self.pop()?)
+    }
+
+    // count değeri çıkarır ve yığındaki sırasıyla (en alttaki önce) döndürür
+    fn pop_many(&mut self, count: usize) -> Result<Vec<Vec<u8>>, ScriptError> {
+        let start = self.stack.len().checked_sub(count).ok_or(ScriptError::StackUnderflow)?;
+        Ok(self.stack.split_off(start))
+    }
+
+    // Karşılaştırma sonucunu yığına koyar; VERIFY türündeki işlemlerde yanlışsa betiği durdurur
+    fn conclude(&mut self, result: bool, verify: bool) -> Result<(), ScriptError> {
+        match (result, verify) {
+            (true, true) => Ok(()),
+            (false, true) => Err(ScriptError::VerifyFailed),
+            (result, false) => self.push(if result { vec![1] } else { Vec::new() }),
+        }
+    }
+}
+
+// Sıfırdan farklı bir byte içeren değer "doğru"dur
+fn is_true(value: &[u8]) -> bool {
+    value.iter().any(|&byte| byte != 0)
+}
+
+// En kısa big-endian biçimdeki sayıyı çözer
+fn parse_number(data: &[u8]) -> Result<u64, ScriptError> {
+    if data.len() > 8 || data.first() == Some(&0) {
+        return Err(ScriptError::InvalidNumber);
+    }
+    Ok(data.iter().fold(0, |value, &byte| (value << 8) | byte as u64))
+}
+
+fn check_signature(signature: &[u8], key: &[u8], message: &[u8]) -> bool {
+    let Ok(key) = <[u8; 32]>::try_from(key) else {
+        return false;
+    };
+    let Ok(signature) = <[u8; 64]>::try_from(signature) else {
+        return false;
+    };
+    VerifyingKey::from_bytes(&key)
+        .is_ok_and(|key| key.verify_strict(message, &Signature::from_bytes(&signature)).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use rand::rngs::OsRng;
+
+    const MESSAGE: &[u8] = b"spend";
+
+    fn sign(key: &SigningKey) -> Vec<u8> {
+        key.sign(MESSAGE).to_bytes().to_vec()
+    }
+
+    fn run(unlocking: &Script, locking: &Script, height: u64) -> Result<(), ScriptError> {
+        execute(unlocking, locking, &ScriptContext { message: MESSAGE, height })
+    }
+
+    #[test]
+    fn encoding_and_assembly_round_trip() {
+        let key = SigningKey::generate(&mut OsRng).verifying_key();
+        let script = Script::timelock(300, &key);
+        assert_eq!(Script::decode(&script.encode()).unwrap(), script);
+        let assembly = script.to_string();
+        assert!(assembly.starts_with("300 OP_CHECKLOCKTIMEVERIFY OP_DROP 0x"));
+        assert_eq!(assembly.parse::<Script>().unwrap(), script);
+        assert_eq!("0 OP_DUP".parse::<Script>().unwrap().encode(), vec![0x00, OP_DUP]);
+        assert_eq!(Script::decode(&[0x05, 0x01]), Err(ScriptError::Malformed));
+        assert_eq!("OP_NOPE".parse::<Script>(), Err(ScriptError::Malformed));
+    }
+
+    #[test]
+    fn oversized_push_is_rejected() {
+        // 75 byte tek uzunluk byte'ına sığar; 76 byte'lık verinin uzunluk byte'ı (0x4c) çözülemeyen bir betik üretirdi
+        let largest = Script::pushes(vec![vec![7; MAX_PUSH_SIZE]]).unwrap();
+        assert_eq!(Script::decode(&largest.encode()).unwrap(), largest);
+        assert_eq!(Script::pushes(vec![vec![7; MAX_PUSH_SIZE + 1]]), Err(ScriptError::PushTooLarge));
+        assert_eq!(Script::new(vec![Op::Push(vec![7; 76]), Op::Drop]), Err(ScriptError::PushTooLarge));
+        assert_eq!(format!("0x{}", "07".repeat(76)).parse::<Script>(), Err(ScriptError::Malformed));
+    }
+
+    #[test]
+    fn pay_to_key_hash_checks_key_and_signature() {
+        let key = SigningKey::generate(&mut OsRng);
+        let public_key = key.verifying_key().as_bytes().to_vec();
+        let locking = Script(vec![
+            Op::Dup,
+            Op::Hash160,
+            Op::Push(hash160(&public_key).to_vec()),
+            Op::EqualVerify,
+            Op::CheckSig,
+        ]);
+        assert!(run(&Script::pushes(vec![sign(&key), public_key.clone()]).unwrap(), &locking, 0).is_ok());
+
+        let mallory = SigningKey::generate(&mut OsRng);
+        let mallory_key = mallory.verifying_key().as_bytes().to_vec();
+        assert_eq!(run(&Script::pushes(vec![sign(&mallory), mallory_key]).unwrap(), &locking, 0), Err(ScriptError::VerifyFailed));
+        assert_eq!(run(&Script::pushes(vec![sign(&mallory), public_key]).unwrap(), &locking, 0), Err(ScriptError::FalseResult));
+    }
+
+    #[test]
+    fn multisig_needs_enough_signatures_in_key_order() {
+        let keys: Vec<SigningKey> = (0..3).map(|_| SigningKey::generate(&mut OsRng)).collect();
+        let public_keys: Vec<VerifyingKey> = keys.iter().map(SigningKey::verifying_key).collect();
+        let locking = Script::multisig(2, &public_keys);
+
+        assert!(run(&Script::pushes(vec![sign(&keys[0]), sign(&keys[2])]).unwrap(), &locking, 0).is_ok());
+        assert_eq!(run(&Script::pushes(vec![sign(&keys[2]), sign(&keys[0])]).unwrap(), &locking, 0), Err(ScriptError::FalseResult));
+        assert_eq!(run(&Script::pushes(vec![sign(&keys[1])]).unwrap(), &locking, 0), Err(ScriptError::StackUnderflow));
+    }
+
+    #[test]
+    fn timelock_opens_at_its_height() {
+        let key = SigningKey::generate(&mut OsRng);
+        let locking = Script::timelock(10, &key.verifying_key());
+        let unlocking = Script::pushes(vec![sign(&key)]).unwrap();
+        assert_eq!(run(&unlocking, &locking, 9), Err(ScriptError::Locked { until: 10, height: 9 }));
+        assert!(run(&unlocking, &locking, 10).is_ok());
+    }
+
+    #[test]
+    fn limits_and_push_only_rule_are_enforced() {
+        let spin = Script(vec![Op::Push(vec![1])].into_iter().chain(std::iter::repeat_n(Op::Dup, MAX_SCRIPT_STEPS)).collect());
+        assert_eq!(run(&Script::default(), &spin, 0), Err(ScriptError::TooManySteps));
+        assert_eq!(run(&Script(vec![Op::Dup]), &Script::default(), 0), Err(ScriptError::NotPushOnly));
+        assert_eq!(run(&Script::default(), &Script(vec![Op::Drop]), 0), Err(ScriptError::StackUnderflow));
+    }
+}
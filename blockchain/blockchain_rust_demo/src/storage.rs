@@ -0,0 +1,231 @@
+// Zinciri diskte saklayan depolama katmanı (JSON-lines formatı)
+// Her satır bir bloğu temsil eder; yeni kazılan bloklar dosyanın sonuna eklenir
+// Program açılırken dosya baştan okunur ve zincir yeniden doğrulanır
+
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::consensus::Consensus;
+use crate::fork::ChainEvent;
+use crate::validation::ValidationReport;
+use crate::{Block, Blockchain};
+
+// Depolama sırasında oluşabilecek hatalar
+#[derive(Debug)]
+pub enum StorageError {
+    Io(io::Error),                           // Dosya okunamadı / yazılamadı
+    Truncated { line: usize },               // Son satır yarım kalmış (yazma sırasında kesinti)
+    Corrupt { line: usize, reason: String }, // Satır geçerli bir blok değil
+    Tampered { index: u32 },                 // Blok içeriği hash'iyle / Merkle köküyle uyuşmuyor ya da sırası bozuk
+    InvalidChain(ValidationReport),          // Bloklar tek tek sağlam ama zincir olarak geçersiz
+}
+
+// Kullanıcıya gösterilecek hata mesajları
+impl Display for StorageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Io(err) => write!(f, "I/O error: {}", err),
+            StorageError::Truncated { line } => {
+                write!(f, "chain file is truncated at line {} (incomplete write?)", line)
+            }
+            StorageError::Corrupt { line, reason } => {
+                write!(f, "chain file is corrupt at line {}: {}", line, reason)
+            }
+            StorageError::Tampered { index } => {
+                write!(f, "block {} has been tampered with (hash or index mismatch)", index)
+            }
+            StorageError::InvalidChain(report) => write!(f, "stored blocks do not form a valid chain: {}", report),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+// ? operatörü ile I/O hatalarının otomatik dönüştürülmesi için
+impl From<io::Error> for StorageError {
+    fn from(err: io::Error) -> Self {
+        StorageError::Io(err)
+    }
+}
+
+impl<C: Consensus> Blockchain<C> {
+    // Dışarıdan gelen (diskten ya da içe aktarılan) bloklardan zinciri kurar ve baştan sona doğrular
+    // Zincirin başlangıç zorluğu genesis bloktan alınır (blok yoksa verilen zorluk kullanılır)
+    pub fn from_blocks(consensus: Arc<C>, bits: u32, blocks: Vec<Block>) -> Result<Blockchain<C>, ValidationReport> {
+        let bits = blocks.first().map_or(bits, |genesis| genesis.bits);
+        let mut blockchain = Blockchain::with_consensus(consensus, bits);
+        blockchain.blocks = blocks;
+        let report = blockchain.validate();
+        if !report.is_valid() {
+            return Err(report);
+        }
+        // Harcanmamış çıktılar saklanmaz, bloklardan yeniden kurulur (defter doğrulamada zaten oynatıldı)
+        blockchain.utxos = blockchain.replay_ledger().expect("validated chain replays cleanly");
+        blockchain.reindex();
+        Ok(blockchain)
+    }
+}
+
+// Zincir dosyasını temsil eder
+#[derive(Clone)]
+pub struct ChainStore {
+    path: PathBuf,
+}
+
+impl ChainStore {
+    pub fn new(path: impl AsRef<Path>) -> ChainStore {
+        ChainStore { path: path.as_ref().to_path_buf() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Dosyadaki zinciri verilen uzlaşma motorunun kurallarıyla yükler ve doğrular
+    // Dosya yoksa verilen başlangıç zorluğuyla boş bir zincir döner
+    pub fn load<C: Consensus>(&self, consensus: Arc<C>, bits: u32) -> Result<Blockchain<C>, StorageError> {
+        let contents = match fs::read(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Blockchain::with_consensus(consensus, bits)),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut blocks = Vec::new();
+        for (i, line) in contents.split_inclusive(|b| *b == b'\n').enumerate() {
+            let line_number = i + 1;
+            // Her blok tek seferde "satır + \n" olarak yazılır; \n yoksa yazma yarıda kalmıştır
+            let Some(line) = line.strip_suffix(b"\n") else {
+                return Err(StorageError::Truncated { line: line_number });
+            };
+            let block: Block = serde_json::from_slice(line).map_err(|err| StorageError::Corrupt {
+                line: line_number,
+                reason: err.to_string(),
+            })?;
+            // Bloğun sırası, hash'i ve mührü içeriğiyle tutarlı olmalı
+            if block.index as usize != blocks.len()
+                || block.hash != block.calculate_hash()
+                || block.merkle_root != block.calculate_merkle_root()
+                || consensus.verify_seal(&block).is_err()
+            {
+                return Err(StorageError::Tampered { index: block.index });
+            }
+            blocks.push(block);
+        }
+
+        Blockchain::from_blocks(consensus, bits, blocks).map_err(StorageError::InvalidChain)
+    }
+
+    // Yeni kazılan bloğu dosyanın sonuna ekler
+    pub fn append(&self, block: &Block) -> Result<(), StorageError> {
+        let mut line = serde_json::to_string(block).map_err(io::Error::from)?;
+        line.push('\n');
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(line.as_bytes())?; // Satır tek parça yazılır, yarım kalırsa yükleme bunu fark eder
+        file.sync_data()?;
+        Ok(())
+    }
+
+    // Zincir yeniden düzenlendiğinde dosyayı baştan yazar
+    // Önce geçici dosyaya yazılır, sonra yeniden adlandırılır; böylece yarım kalan yazma eski zinciri bozmaz
+    pub fn rewrite(&self, blocks: &[Block]) -> Result<(), StorageError> {
+        let mut contents = String::new();
+        for block in blocks {
+            contents.push_str(&serde_json::to_string(block).map_err(io::Error::from)?);
+            contents.push('\n');
+        }
+        let temporary = self.path.with_extension("tmp");
+        let mut file = OpenOptions::new().create(true).write(true).truncate(true).open(&temporary)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_data()?;
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+
+    // Bir bloğun zincirde yol açtığı değişiklikleri diske yansıtır
+    // Uca eklenen bloklar dosyanın sonuna yazılır; zincir yeniden düzenlendiyse dosya baştan yazılır
+    pub fn save<C: Consensus>(&self, blockchain: &Blockchain<C>, events: &[ChainEvent]) -> Result<(), StorageError> {
+        if events.iter().any(|event| matches!(event, ChainEvent::Reorganized { .. })) {
+            return self.rewrite(&blockchain.blocks);
+        }
+        for event in events {
+            if let ChainEvent::Extended { hash } = event
+                && let Some(block) = blockchain.blocks.iter().rev().find(|block| &block.hash == hash)
+            {
+                self.append(block)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::ProofOfWork;
+    use crate::difficulty::EASIEST_BITS;
+    use crate::tests::{mine_block, temp_store};
+    use crate::transaction::TxOutput;
+    use crate::wallet::address_of;
+    use ed25519_dalek::SigningKey;
+    use rand::rngs::OsRng;
+
+    fn saved_chain(store: &ChainStore) -> Blockchain {
+        let key = SigningKey::generate(&mut OsRng);
+        let miner = address_of(&key.verifying_key());
+        let mut blockchain = Blockchain::with_difficulty(EASIEST_BITS);
+        for amount in [0, 5, 2] {
+            let mut transactions = Vec::new();
+            if amount > 0 {
+                let payment = vec![TxOutput { amount, address: String::from("bob"), script: None }];
+                transactions.push(blockchain.utxos.build_transaction(&key, payment, 0).unwrap());
+            }
+            mine_block(&mut blockchain, &miner, transactions).unwrap();
+            store.append(blockchain.blocks.last().unwrap()).unwrap();
+        }
+        blockchain
+    }
+
+    #[test]
+    fn reloads_saved_chain() {
+        let store = temp_store("storage-reload");
+        let saved = saved_chain(&store);
+        let loaded = store.load(Arc::new(ProofOfWork), EASIEST_BITS).unwrap();
+        let hashes = |chain: &Blockchain| chain.blocks.iter().map(|b| b.hash).collect::<Vec<_>>();
+        assert_eq!(hashes(&loaded), hashes(&saved));
+        assert!(loaded.validate().is_valid());
+        assert_eq!(loaded.balance_of("bob"), 7);
+        fs::remove_file(store.path()).unwrap();
+    }
+
+    #[test]
+    fn rewrite_replaces_file_contents() {
+        let store = temp_store("storage-rewrite");
+        let saved = saved_chain(&store);
+        store.rewrite(&saved.blocks[..2]).unwrap();
+        assert_eq!(store.load(Arc::new(ProofOfWork), EASIEST_BITS).unwrap().blocks.len(), 2);
+        fs::remove_file(store.path()).unwrap();
+    }
+
+    #[test]
+    fn detects_truncated_file() {
+        let store = temp_store("storage-truncated");
+        saved_chain(&store);
+        let contents = fs::read(store.path()).unwrap();
+        fs::write(store.path(), &contents[..contents.len() - 10]).unwrap();
+        assert!(matches!(store.load(Arc::new(ProofOfWork), EASIEST_BITS), Err(StorageError::Truncated { line: 3 })));
+        fs::remove_file(store.path()).unwrap();
+    }
+
+    #[test]
+    fn detects_tampered_block() {
+        let store = temp_store("storage-tampered");
+        saved_chain(&store);
+        let contents = fs::read_to_string(store.path()).unwrap();
+        fs::write(store.path(), contents.replace("\"amount\":5,", "\"amount\":500,")).unwrap();
+        assert!(matches!(store.load(Arc::new(ProofOfWork), EASIEST_BITS), Err(StorageError::Tampered { index: 1 })));
+        fs::remove_file(store.path()).unwrap();
+    }
+}
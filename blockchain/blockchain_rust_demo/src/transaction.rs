@@ -0,0 +1,235 @@
+// İmzalı işlemler (transaction): önceki çıktıları harcayan girdiler ve yeni çıktılar (UTXO modeli)
+// Her girdi, harcadığı çıktının sahibine ait Ed25519 açık anahtarı ve imzayı taşır
+// Adres açık anahtardan türetildiği için başkasının çıktısı imzalanarak harcanamaz
+// Bir çıktı kilitleme betiği de taşıyabilir; o zaman girdi imza yerine betiği açan bir betik verir (bkz. script.rs)
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::script::Script;
+use crate::wallet::address_of;
+use crate::{from_hex, to_hex};
+
+// Daha önce oluşmuş bir çıktının adresi: hangi işlemin kaçıncı çıktısı
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct OutPoint {
+    pub txid: String, // Çıktıyı oluşturan işlemin kimliği (hex)
+    pub index: u32,   // İşlemdeki çıktı sırası
+}
+
+// Harcanan bir çıktı ve harcama yetkisinin kanıtı
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TxInput {
+    pub previous_output: OutPoint, // Harcanan çıktı
+    pub public_key: String,        // Çıktının sahibinin Ed25519 açık anahtarı (hex)
+    pub signature: String,         // İşlem içeriğinin imzası (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlocking: Option<Script>, // Betikli çıktıyı açan betik (varsa açık anahtar ve imza boştur)
+}
+
+// Bir adrese gönderilen miktar
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TxOutput {
+    pub amount: u64,            // Gönderilen miktar
+    pub address: String,        // Alıcının adresi (betikli çıktıda betiğin adresi)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<Script>, // Kilitleme betiği (yoksa çıktı adresin anahtarıyla imzalanarak harcanır)
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    pub inputs: Vec<TxInput>,   // Harcanan çıktılar (coinbase işleminde boş)
+    pub outputs: Vec<TxOutput>, // Oluşturulan yeni çıktılar
+    pub nonce: u64,             // Coinbase'de blok yüksekliği; aynı çıktılara sahip işlemlerin kimliğini ayırır
+}
+
+impl Transaction {
+    // Madenciye blok ödülünü veren, girdisi olmayan işlem
+    pub fn coinbase(address: String, amount: u64, height: u64) -> Transaction {
+        Transaction {
+            inputs: Vec::new(),
+            outputs: vec![TxOutput { amount, address, script: None }],
+            nonce: height,
+        }
+    }
+
+    // Verilen çıktıları harcayan yeni bir işlem oluşturur ve her girdiyi gönderenin anahtarıyla imzalar
+    pub fn new_signed(key: &SigningKey, spent: Vec<OutPoint>, outputs: Vec<TxOutput>) -> Transaction {
+        let public_key = to_hex(key.verifying_key().as_bytes());
+        let inputs = spent
+            .into_iter()
+            .map(|previous_output| TxInput {
+                previous_output,
+                public_key: public_key.clone(),
+                signature: String::new(),
+                unlocking: None,
+            })
+            .collect();
+        let mut transaction = Transaction { inputs, outputs, nonce: 0 };
+        let signature = to_hex(&key.sign(&transaction.signing_bytes()).to_bytes());
+        for input in &mut transaction.inputs {
+            input.signature = signature.clone();
+        }
+        transaction
+    }
+
+    // Betikli çıktıları harcayan bir işlem oluşturur
+    // Her girdinin açma betiği, verilen anahtarların işlem içeriğine attığı imzaları sırayla yığına koyar
+    // (çoklu imzada anahtarlar kilitleme betiğindeki sırayla verilmelidir)
+    pub fn new_script_spend(keys: &[SigningKey], spent: Vec<OutPoint>, outputs: Vec<TxOutput>) -> Transaction {
+        let inputs = spent
+            .into_iter()
+            .map(|previous_output| TxInput {
+                previous_output,
+                public_key: String::new(),
+                signature: String::new(),
+                unlocking: None,
+            })
+            .collect();
+        let mut transaction = Transaction { inputs, outputs, nonce: 0 };
+        let message = transaction.signing_bytes();
+        let signatures = keys.iter().map(|key| key.sign(&message).to_bytes().to_vec()).collect();
+        let unlocking = Script::pushes(signatures).expect("a 64-byte signature fits in a single push");
+        for input in &mut transaction.inputs {
+            input.unlocking = Some(unlocking.clone());
+        }
+        transaction
+    }
+
+    pub fn is_coinbase(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    // İmzalanan içerik: imzalar ve açma betikleri hariç tüm alanlar
+    // Her metin alanının önüne uzunluğu yazılır, böylece alanlar birbirine karışamaz
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        fn push_str(bytes: &mut Vec<u8>, field: &str) {
+            bytes.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            bytes.extend_from_slice(field.as_bytes());
+        }
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(self.inputs.len() as u64).to_be_bytes());
+        for input in &self.inputs {
+            push_str(&mut bytes, &input.previous_output.txid);
+            bytes.extend_from_slice(&input.previous_output.index.to_be_bytes());
+            push_str(&mut bytes, &input.public_key);
+        }
+        bytes.extend_from_slice(&(self.outputs.len() as u64).to_be_bytes());
+        for output in &self.outputs {
+            bytes.extend_from_slice(&output.amount.to_be_bytes());
+            push_str(&mut bytes, &output.address);
+        }
+        bytes.extend_from_slice(&self.nonce.to_be_bytes());
+        // Kilitleme betikleri en sona, sadece varsa eklenir; betiksiz işlemlerin kimlikleri değişmez
+        for (index, output) in self.outputs.iter().enumerate() {
+            if let Some(script) = &output.script {
+                let encoded = script.encode();
+                bytes.extend_from_slice(&(index as u64).to_be_bytes());
+                bytes.extend_from_slice(&(encoded.len() as u64).to_be_bytes());
+                bytes.extend_from_slice(&encoded);
+            }
+        }
+        bytes
+    }
+
+    // İşlemin kimliği: imzalar ve açma betikleri dahil tüm içeriğin SHA256 hash'i
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.signing_bytes());
+        for input in &self.inputs {
+            hasher.update(input.signature.as_bytes());
+        }
+        for input in &self.inputs {
+            if let Some(unlocking) = &input.unlocking {
+                hasher.update(unlocking.encode());
+            }
+        }
+        hasher.finalize().into()
+    }
+
+    // Çıktılarda ve UTXO kümesinde kullanılan hex kimlik
+    pub fn txid(&self) -> String {
+        to_hex(&self.hash())
+    }
+
+    // Toplam çıktı miktarı (taşma durumunda None)
+    pub fn output_total(&self) -> Option<u64> {
+        self.outputs.iter().try_fold(0u64, |total, output| total.checked_add(output.amount))
+    }
+
+    // Tüm girdilerin imzaları geçerli mi? (Çıktıların gerçekten bu anahtarlara ait olduğu
+    // ve harcanmamış olduğu UTXO kümesi tarafından ayrıca kontrol edilir)
+    // Açma betiği taşıyan girdiler burada atlanır; onları UTXO kümesi harcanan çıktının betiğiyle birlikte çalıştırır
+    pub fn verify(&self) -> bool {
+        let message = self.signing_bytes();
+        self.inputs.iter().filter(|input| input.unlocking.is_none()).all(|input| {
+            let public_key = parse_public_key(&input.public_key);
+            let signature = from_hex(&input.signature)
+                .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+                .map(|bytes| Signature::from_bytes(&bytes));
+            match (public_key, signature) {
+                (Some(public_key), Some(signature)) => public_key.verify_strict(&message, &signature).is_ok(),
+                _ => false,
+            }
+        })
+    }
+}
+
+impl TxInput {
+    // Girdiyi imzalayan anahtarın adresi (harcanan çıktının adresiyle aynı olmalı)
+    pub fn owner(&self) -> Option<String> {
+        parse_public_key(&self.public_key).map(|public_key| address_of(&public_key))
+    }
+}
+
+// Hex açık anahtarı çözer
+fn parse_public_key(hex: &str) -> Option<VerifyingKey> {
+    from_hex(hex)
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    fn payment(amount: u64) -> Vec<TxOutput> {
+        vec![TxOutput { amount, address: String::from("bob"), script: None }]
+    }
+
+    fn outpoint() -> OutPoint {
+        OutPoint { txid: "00".repeat(32), index: 0 }
+    }
+
+    #[test]
+    fn signed_transaction_verifies() {
+        let key = SigningKey::generate(&mut OsRng);
+        let transaction = Transaction::new_signed(&key, vec![outpoint()], payment(5));
+        assert!(transaction.verify());
+        assert_eq!(transaction.inputs[0].owner(), Some(address_of(&key.verifying_key())));
+    }
+
+    #[test]
+    fn modified_transaction_fails_verification() {
+        let key = SigningKey::generate(&mut OsRng);
+        let mut transaction = Transaction::new_signed(&key, vec![outpoint()], payment(5));
+        transaction.outputs[0].amount = 500;
+        assert!(!transaction.verify());
+
+        // Başka birinin anahtarıyla imzalanmış girdiye kendi açık anahtarını koymak da işe yaramaz
+        let mallory = SigningKey::generate(&mut OsRng);
+        let mut forged = Transaction::new_signed(&mallory, vec![outpoint()], payment(5));
+        forged.inputs[0].public_key = to_hex(key.verifying_key().as_bytes());
+        assert!(!forged.verify());
+    }
+
+    #[test]
+    fn coinbase_ids_differ_by_height() {
+        let first = Transaction::coinbase(String::from("miner"), 50, 0);
+        let second = Transaction::coinbase(String::from("miner"), 50, 1);
+        assert!(first.is_coinbase() && first.verify());
+        assert_ne!(first.txid(), second.txid());
+    }
+}
@@ -0,0 +1,379 @@
+// UTXO (harcanmamış işlem çıktıları) defteri
+// Her blok eklendiğinde harcanan çıktılar kümeden silinir, yeni çıktılar eklenir
+// Bir çıktı kümede yoksa ya hiç oluşmamıştır ya da daha önce harcanmıştır (çifte harcama)
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+
+use ed25519_dalek::SigningKey;
+
+use crate::script::{self, ScriptContext, ScriptError};
+use crate::transaction::{OutPoint, Transaction, TxOutput};
+use crate::wallet::{address_of, script_address};
+
+// Her bloğun ilk işlemiyle madenciye verilen ödül (işlem ücretleri buna eklenir)
+pub const BLOCK_REWARD: u64 = 50;
+
+// Bir blok ya da işlem deftere uygulanamadığında dönen hatalar
+#[derive(Debug)]
+pub enum LedgerError {
+    MissingCoinbase,                                   // Bloğun ilk işlemi coinbase değil
+    UnexpectedCoinbase { txid: String },               // Coinbase ilk sırada değil ya da yüksekliği yanlış
+    ExcessiveReward { claimed: u64, allowed: u64 },    // Madenci ödül + ücretten fazlasını almış
+    InvalidSignature { txid: String },                 // Girdilerden birinin imzası geçersiz
+    MissingOutput { txid: String, spent: OutPoint },   // Harcanan çıktı yok (çifte harcama)
+    WrongOwner { txid: String, spent: OutPoint },      // Çıktıyı sahibi olmayan biri harcamaya çalışmış
+    Overspend { txid: String, inputs: u64, outputs: u64 }, // Çıktılar girdilerden fazla
+    InsufficientFunds { available: u64, required: u64 },   // Cüzdanda yeterli bakiye yok
+    ScriptFailed { txid: String, spent: OutPoint, reason: ScriptError }, // Betikli çıktının betiği harcamayı onaylamadı
+    ScriptAddressMismatch { txid: String, index: usize },  // Betikli çıktının adresi betiğinden türetilen adres değil
+    Overflow,                                          // Miktarlar toplanırken taşma oldu
+}
+
+impl Display for LedgerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::MissingCoinbase => write!(f, "first transaction of the block is not a coinbase"),
+            LedgerError::UnexpectedCoinbase { txid } => write!(f, "unexpected coinbase transaction {}", txid),
+            LedgerError::ExcessiveReward { claimed, allowed } => {
+                write!(f, "coinbase claims {} but only {} is allowed", claimed, allowed)
+            }
+            LedgerError::InvalidSignature { txid } => write!(f, "transaction {} has an invalid signature", txid),
+            LedgerError::MissingOutput { txid, spent } => write!(
+                f,
+                "transaction {} spends {}:{} which does not exist or is already spent",
+                txid, spent.txid, spent.index
+            ),
+            LedgerError::WrongOwner { txid, spent } => write!(
+                f,
+                "transaction {} spends {}:{} without owning it",
+                txid, spent.txid, spent.index
+            ),
+            LedgerError::Overspend { txid, inputs, outputs } => {
+                write!(f, "transaction {} spends {} but only has {} in inputs", txid, outputs, inputs)
+            }
+            LedgerError::InsufficientFunds { available, required } => {
+                write!(f, "insufficient funds: {} available, {} required", available, required)
+            }
+            LedgerError::ScriptFailed { txid, spent, reason } => write!(
+                f,
+                "transaction {} cannot spend {}:{}: {}",
+                txid, spent.txid, spent.index, reason
+            ),
+            LedgerError::ScriptAddressMismatch { txid, index } => {
+                write!(f, "output {} of transaction {} is not paid to its script's address", index, txid)
+            }
+            LedgerError::Overflow => write!(f, "amount overflow"),
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+// Harcanmamış çıktılar kümesi (sıralı tutulur, böylece cüzdan seçimleri tekrarlanabilir olur)
+#[derive(Clone, Default)]
+pub struct UtxoSet {
+    outputs: BTreeMap<OutPoint, TxOutput>,
+    height: u64, // İşlemlerin gireceği bloğun yüksekliği (zaman kilitli betikler buna göre açılır)
+}
+
+impl UtxoSet {
+    // Bir bloğun işlemlerini kontrol eder ve kümeye uygular
+    // Hata olursa küme değişmeden kalır
+    pub fn apply_block(&mut self, transactions: &[Transaction], height: u64) -> Result<(), LedgerError> {
+        let Some((coinbase, rest)) = transactions.split_first() else {
+            return Err(LedgerError::MissingCoinbase);
+        };
+        if !coinbase.is_coinbase() {
+            return Err(LedgerError::MissingCoinbase);
+        }
+        // Coinbase yüksekliği taşır; böylece her bloğun coinbase kimliği farklı olur
+        if coinbase.nonce != height {
+            return Err(LedgerError::UnexpectedCoinbase { txid: coinbase.txid() });
+        }
+        check_script_addresses(coinbase)?;
+
+        let mut staged = self.clone();
+        staged.height = height;
+        let fees = staged.apply_transactions(rest)?;
+        let allowed = BLOCK_REWARD.checked_add(fees).ok_or(LedgerError::Overflow)?;
+        let claimed = coinbase.output_total().ok_or(LedgerError::Overflow)?;
+        if claimed > allowed {
+            return Err(LedgerError::ExcessiveReward { claimed, allowed });
+        }
+        staged.add_outputs(coinbase);
+        staged.height = height + 1;
+        *self = staged;
+        Ok(())
+    }
+
+    // Coinbase olmayan işlemleri sırayla uygular ve toplam işlem ücretini döndürür
+    // Aynı bloktaki bir işlem, kendinden önceki işlemin çıktısını harcayabilir
+    pub fn apply_transactions(&mut self, transactions: &[Transaction]) -> Result<u64, LedgerError> {
+        let mut fees: u64 = 0;
+        for transaction in transactions {
+            let fee = self.validate_transaction(transaction)?;
+            fees = fees.checked_add(fee).ok_or(LedgerError::Overflow)?;
+            for input in &transaction.inputs {
+                self.outputs.remove(&input.previous_output);
+            }
+            self.add_outputs(transaction);
+        }
+        Ok(fees)
+    }
+
+    // İşlem bu kümeye göre geçerli mi? Geçerliyse işlem ücretini (girdiler - çıktılar) döndürür
+    pub fn validate_transaction(&self, transaction: &Transaction) -> Result<u64, LedgerError> {
+        let txid = transaction.txid();
+        if transaction.is_coinbase() {
+            return Err(LedgerError::UnexpectedCoinbase { txid });
+        }
+        if !transaction.verify() {
+            return Err(LedgerError::InvalidSignature { txid });
+        }
+        check_script_addresses(transaction)?;
+
+        let message = transaction.signing_bytes();
+        let context = ScriptContext { message: &message, height: self.height };
+        let mut spent = BTreeSet::new();
+        let mut inputs: u64 = 0;
+        for input in &transaction.inputs {
+            let previous_output = &input.previous_output;
+            // Aynı çıktıyı aynı işlemde iki kez harcamak da çifte harcamadır
+            let output = self.outputs.get(previous_output).filter(|_| spent.insert(previous_output));
+            let Some(output) = output else {
+                return Err(LedgerError::MissingOutput { txid, spent: previous_output.clone() });
+            };
+            // Betiksiz çıktıyı sahibinin imzası, betikli çıktıyı ise betiği açan bir girdi harcar
+            match (&output.script, &input.unlocking) {
+                (None, None) if input.owner().as_ref() == Some(&output.address) => {}
+                (Some(locking), Some(unlocking)) => {
+                    if let Err(reason) = script::execute(unlocking, locking, &context) {
+                        return Err(LedgerError::ScriptFailed { txid, spent: previous_output.clone(), reason });
+                    }
+                }
+                _ => return Err(LedgerError::WrongOwner { txid, spent: previous_output.clone() }),
+            }
+            inputs = inputs.checked_add(output.amount).ok_or(LedgerError::Overflow)?;
+        }
+
+        let outputs = transaction.output_total().ok_or(LedgerError::Overflow)?;
+        if outputs > inputs {
+            return Err(LedgerError::Overspend { txid, inputs, outputs });
+        }
+        Ok(inputs - outputs)
+    }
+
+    // İşlemin çıktılarını kümeye ekler
+    fn add_outputs(&mut self, transaction: &Transaction) {
+        let txid = transaction.txid();
+        for (index, output) in transaction.outputs.iter().enumerate() {
+            let outpoint = OutPoint { txid: txid.clone(), index: index as u32 };
+            self.outputs.insert(outpoint, output.clone());
+        }
+    }
+
+    // Harcanmamış bir çıktı (harcanmışsa ya da hiç oluşmamışsa None)
+    pub fn output(&self, outpoint: &OutPoint) -> Option<&TxOutput> {
+        self.outputs.get(outpoint)
+    }
+
+    // Adrese ait harcanmamış çıktılar
+    pub fn unspent_for<'a>(&'a self, address: &'a str) -> impl Iterator<Item = (&'a OutPoint, &'a TxOutput)> {
+        self.outputs.iter().filter(move |(_, output)| output.address == address)
+    }
+
+    // Adresin bakiyesi: sahip olduğu harcanmamış çıktıların toplamı
+    pub fn balance_of(&self, address: &str) -> u64 {
+        self.unspent_for(address).map(|(_, output)| output.amount).sum()
+    }
+
+    // Anahtarın sahibi olduğu çıktılardan ödemeleri ve işlem ücretini karşılayan imzalı bir işlem hazırlar
+    // Ücret çıktılara yazılmaz (girdiler - çıktılar farkı olarak madenciye kalır)
+    // Fazla kalan miktar gönderene para üstü (change) olarak geri döner
+    pub fn build_transaction(&self, key: &SigningKey, mut payments: Vec<TxOutput>, fee: u64) -> Result<Transaction, LedgerError> {
+        let address = address_of(&key.verifying_key());
+        let required = payments
+            .iter()
+            .try_fold(fee, |total, payment| total.checked_add(payment.amount))
+            .ok_or(LedgerError::Overflow)?;
+
+        let mut spent = Vec::new();
+        let mut available: u64 = 0;
+        for (outpoint, output) in self.unspent_for(&address) {
+            if available >= required {
+                break;
+            }
+            spent.push(outpoint.clone());
+            available += output.amount;
+        }
+        if available < required || spent.is_empty() {
+            return Err(LedgerError::InsufficientFunds { available, required });
+        }
+
+        if available > required {
+            payments.push(TxOutput { amount: available - required, address, script: None });
+        }
+        Ok(Transaction::new_signed(key, spent, payments))
+    }
+}
+
+// Betikli çıktılar betiğin adresine yazılmalı; böylece adres, çıktıyı neyin açacağını da belirler
+fn check_script_addresses(transaction: &Transaction) -> Result<(), LedgerError> {
+    let mismatch = transaction.outputs.iter().position(|output| {
+        output.script.as_ref().is_some_and(|script| script_address(script) != output.address)
+    });
+    match mismatch {
+        Some(index) => Err(LedgerError::ScriptAddressMismatch { txid: transaction.txid(), index }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script::Script;
+    use rand::rngs::OsRng;
+
+    fn pay(address: &str, amount: u64) -> Vec<TxOutput> {
+        vec![TxOutput { amount, address: address.to_string(), script: None }]
+    }
+
+    // Anahtara ilk bloğun ödülünü veren küme
+    fn funded(key: &SigningKey) -> UtxoSet {
+        let mut utxos = UtxoSet::default();
+        let coinbase = Transaction::coinbase(address_of(&key.verifying_key()), BLOCK_REWARD, 0);
+        utxos.apply_block(&[coinbase], 0).unwrap();
+        utxos
+    }
+
+    #[test]
+    fn transfer_moves_value_and_returns_change() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let mut utxos = funded(&alice);
+        let transaction = utxos.build_transaction(&alice, pay("bob", 20), 0).unwrap();
+        let miner = Transaction::coinbase(String::from("miner"), BLOCK_REWARD, 1);
+        utxos.apply_block(&[miner, transaction], 1).unwrap();
+        assert_eq!(utxos.balance_of("bob"), 20);
+        assert_eq!(utxos.balance_of(&address_of(&alice.verifying_key())), 30);
+        assert_eq!(utxos.balance_of("miner"), BLOCK_REWARD);
+    }
+
+    #[test]
+    fn fee_is_left_for_the_miner() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let mut utxos = funded(&alice);
+        let transaction = utxos.build_transaction(&alice, pay("bob", 20), 3).unwrap();
+        assert_eq!(utxos.validate_transaction(&transaction).unwrap(), 3);
+        let miner = Transaction::coinbase(String::from("miner"), BLOCK_REWARD + 3, 1);
+        utxos.apply_block(&[miner, transaction], 1).unwrap();
+        assert_eq!(utxos.balance_of(&address_of(&alice.verifying_key())), 27);
+        assert_eq!(utxos.balance_of("miner"), BLOCK_REWARD + 3);
+    }
+
+    #[test]
+    fn double_spend_is_rejected() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let mut utxos = funded(&alice);
+        let first = utxos.build_transaction(&alice, pay("bob", 50), 0).unwrap();
+        let second = utxos.build_transaction(&alice, pay("carol", 50), 0).unwrap();
+        let coinbase = Transaction::coinbase(String::from("miner"), BLOCK_REWARD, 1);
+        let result = utxos.apply_block(&[coinbase, first, second], 1);
+        assert!(matches!(result, Err(LedgerError::MissingOutput { .. })));
+        // Başarısız blok kümeyi değiştirmez
+        assert_eq!(utxos.balance_of("bob"), 0);
+    }
+
+    #[test]
+    fn overspend_and_excessive_reward_are_rejected() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let utxos = funded(&alice);
+        assert!(matches!(
+            utxos.build_transaction(&alice, pay("bob", 51), 0),
+            Err(LedgerError::InsufficientFunds { available: 50, required: 51 })
+        ));
+
+        let mut transaction = utxos.build_transaction(&alice, pay("bob", 50), 0).unwrap();
+        transaction.outputs[0].amount = 60; // İmza da bozulur, önce imza hatası yakalanır
+        assert!(matches!(utxos.validate_transaction(&transaction), Err(LedgerError::InvalidSignature { .. })));
+        // Aynı fazla harcama doğru imzalanınca defter kontrolüne takılır
+        let address = address_of(&alice.verifying_key());
+        let (outpoint, _) = utxos.unspent_for(&address).next().unwrap();
+        let overspend = Transaction::new_signed(&alice, vec![outpoint.clone()], pay("bob", 60));
+        assert!(matches!(utxos.validate_transaction(&overspend), Err(LedgerError::Overspend { .. })));
+
+        let greedy = Transaction::coinbase(String::from("miner"), BLOCK_REWARD + 1, 1);
+        assert!(matches!(utxos.clone().apply_block(&[greedy], 1), Err(LedgerError::ExcessiveReward { .. })));
+    }
+
+    #[test]
+    fn spending_someone_elses_output_is_rejected() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let mallory = SigningKey::generate(&mut OsRng);
+        let utxos = funded(&alice);
+        let address = address_of(&alice.verifying_key());
+        let (outpoint, _) = utxos.unspent_for(&address).next().unwrap();
+        let theft = Transaction::new_signed(&mallory, vec![outpoint.clone()], pay("mallory", 50));
+        assert!(matches!(utxos.validate_transaction(&theft), Err(LedgerError::WrongOwner { .. })));
+    }
+
+    // Alice'in ödülünü verilen betiğe kilitleyen blok uygulanmış küme ve kilitli çıktı
+    fn locked(alice: &SigningKey, script: Script) -> (UtxoSet, OutPoint) {
+        let mut utxos = funded(alice);
+        let payment = vec![TxOutput { amount: BLOCK_REWARD, address: script_address(&script), script: Some(script) }];
+        let lock = utxos.build_transaction(alice, payment, 0).unwrap();
+        let outpoint = OutPoint { txid: lock.txid(), index: 0 };
+        let coinbase = Transaction::coinbase(String::from("miner"), BLOCK_REWARD, 1);
+        utxos.apply_block(&[coinbase, lock], 1).unwrap();
+        (utxos, outpoint)
+    }
+
+    #[test]
+    fn multisig_output_needs_all_required_signers() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let bob = SigningKey::generate(&mut OsRng);
+        let script = Script::multisig(2, &[alice.verifying_key(), bob.verifying_key()]);
+        let (utxos, outpoint) = locked(&alice, script);
+
+        let alone = Transaction::new_script_spend(std::slice::from_ref(&alice), vec![outpoint.clone()], pay("carol", 50));
+        assert!(matches!(utxos.validate_transaction(&alone), Err(LedgerError::ScriptFailed { .. })));
+        // Betikli çıktı sahibinin imzasıyla da harcanamaz
+        let signed = Transaction::new_signed(&alice, vec![outpoint.clone()], pay("carol", 50));
+        assert!(matches!(utxos.validate_transaction(&signed), Err(LedgerError::WrongOwner { .. })));
+
+        let mut both = Transaction::new_script_spend(&[alice, bob], vec![outpoint], pay("carol", 50));
+        assert_eq!(utxos.validate_transaction(&both).unwrap(), 0);
+        // İmzalar işlemin içeriğine bağlıdır; alıcıyı değiştiren betiği bozar
+        both.outputs[0].address = String::from("mallory");
+        assert!(matches!(utxos.validate_transaction(&both), Err(LedgerError::ScriptFailed { .. })));
+    }
+
+    #[test]
+    fn timelocked_output_is_spendable_from_its_height() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let (mut utxos, outpoint) = locked(&alice, Script::timelock(3, &alice.verifying_key()));
+        let spend = Transaction::new_script_spend(&[alice], vec![outpoint], pay("bob", 50));
+        assert!(matches!(
+            utxos.validate_transaction(&spend),
+            Err(LedgerError::ScriptFailed { reason: ScriptError::Locked { until: 3, height: 2 }, .. })
+        ));
+
+        utxos.apply_block(&[Transaction::coinbase(String::from("miner"), BLOCK_REWARD, 2)], 2).unwrap();
+        let coinbase = Transaction::coinbase(String::from("miner"), BLOCK_REWARD, 3);
+        utxos.apply_block(&[coinbase, spend], 3).unwrap();
+        assert_eq!(utxos.balance_of("bob"), 50);
+    }
+
+    #[test]
+    fn scripted_output_must_pay_to_its_script_address() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let utxos = funded(&alice);
+        let script = Some(Script::timelock(0, &alice.verifying_key()));
+        let payment = vec![TxOutput { amount: 50, address: String::from("bob"), script }];
+        let transaction = utxos.build_transaction(&alice, payment, 0).unwrap();
+        assert!(matches!(
+            utxos.validate_transaction(&transaction),
+            Err(LedgerError::ScriptAddressMismatch { index: 0, .. })
+        ));
+    }
+}
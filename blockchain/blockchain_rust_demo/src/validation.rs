@@ -0,0 +1,219 @@
+// Zincirin ayrıntılı doğrulanması
+// Doğrulama ilk hatada durmaz; her bloktaki her kural ihlali, bloğun sırası ve nedeniyle birlikte raporlanır
+// Böylece kullanıcı zincirin tam olarak nerede ve neden bozulduğunu görebilir
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::consensus::{Consensus, SealError};
+use crate::header::ZERO_HASH;
+use crate::merkle::Hash;
+use crate::utxo::{LedgerError, UtxoSet};
+use crate::{to_hex, Block, Blockchain};
+
+// Bir bloğun çiğnediği kural
+#[derive(Debug)]
+pub enum ViolationKind {
+    BadGenesis { previous_block_hash: Hash },            // Genesis bloğun önceki hash'i sıfır değil
+    BadIndex { expected: usize, found: u32 },            // Bloğun indeksi zincirdeki sırasıyla uyuşmuyor
+    BrokenLink { expected: Hash, found: Hash },          // Önceki hash, önceki bloğun hash'i değil
+    BadHash { expected: Hash, found: Hash },             // Saklanan hash, başlığın yeniden hesaplanan hash'i değil
+    InvalidSeal(SealError),                              // Mühür uzlaşma kurallarına uymuyor (iş ya da imza)
+    WrongDifficulty { expected: u32, found: u32 },       // Zorluk ayarlama takvimiyle uyuşmuyor
+    NonMonotonicTimestamp { previous: u64, found: u64 }, // Zaman damgası önceki bloğunkinden küçük
+    MerkleRootMismatch,                                  // Merkle kökü işlemlerle uyuşmuyor
+    Ledger(LedgerError),                                 // İşlemler deftere uygulanamıyor
+}
+
+impl Display for ViolationKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ViolationKind::BadGenesis { previous_block_hash } => {
+                write!(f, "genesis block points to previous hash {} instead of zero", to_hex(previous_block_hash))
+            }
+            ViolationKind::BadIndex { expected, found } => write!(f, "bad index: expected {}, found {}", expected, found),
+            ViolationKind::BrokenLink { expected, found } => {
+                write!(f, "broken link: previous hash is {} but the previous block is {}", to_hex(found), to_hex(expected))
+            }
+            ViolationKind::BadHash { expected, found } => {
+                write!(f, "bad hash: stored {} but the header hashes to {}", to_hex(found), to_hex(expected))
+            }
+            ViolationKind::InvalidSeal(err) => write!(f, "invalid seal: {}", err),
+            ViolationKind::WrongDifficulty { expected, found } => {
+                write!(f, "wrong difficulty: expected {:#010x}, found {:#010x}", expected, found)
+            }
+            ViolationKind::NonMonotonicTimestamp { previous, found } => {
+                write!(f, "non-monotonic timestamp: {} is earlier than the previous block's {}", found, previous)
+            }
+            ViolationKind::MerkleRootMismatch => write!(f, "merkle root does not match the transactions"),
+            ViolationKind::Ledger(err) => write!(f, "ledger error: {}", err),
+        }
+    }
+}
+
+// Zincirdeki sırasıyla birlikte bir kural ihlali
+#[derive(Debug)]
+pub struct Violation {
+    pub index: usize, // Bloğun zincirdeki sırası (bloğun kendi beyan ettiği indeks değil)
+    pub kind: ViolationKind,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "block {}: {}", self.index, self.kind)
+    }
+}
+
+// Doğrulamanın sonucu: ihlal yoksa zincir geçerlidir
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    fn push(&mut self, index: usize, kind: ViolationKind) {
+        self.violations.push(Violation { index, kind });
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return write!(f, "chain is valid");
+        }
+        write!(f, "{} violation(s) found", self.violations.len())?;
+        for violation in &self.violations {
+            write!(f, "\n  {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl<C: Consensus> Blockchain<C> {
+    // Zincirdeki tüm blokları (genesis dahil) kontrol eder ve bulunan tüm ihlalleri döndürür
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let mut utxos = Some(UtxoSet::default());
+
+        for (i, block) in self.blocks.iter().enumerate() {
+            if block.index as usize != i {
+                report.push(i, ViolationKind::BadIndex { expected: i, found: block.index });
+            }
+
+            match i.checked_sub(1).map(|previous| &self.blocks[previous]) {
+                None if block.previous_block_hash != ZERO_HASH => {
+                    let previous_block_hash = block.previous_block_hash;
+                    report.push(i, ViolationKind::BadGenesis { previous_block_hash });
+                }
+                None => {}
+                Some(previous_block) => {
+                    if block.previous_block_hash != previous_block.hash {
+                        let expected = previous_block.hash;
+                        let found = block.previous_block_hash;
+                        report.push(i, ViolationKind::BrokenLink { expected, found });
+                    }
+                    if block.timestamp < previous_block.timestamp {
+                        let (previous, found) = (previous_block.timestamp, block.timestamp);
+                        report.push(i, ViolationKind::NonMonotonicTimestamp { previous, found });
+                    }
+                }
+            }
+
+            let expected = block.calculate_hash();
+            if block.hash != expected {
+                report.push(i, ViolationKind::BadHash { expected, found: block.hash });
+            }
+            // Mühür başlığın gerçek hash'i üzerinden kontrol edilir (saklanan hash bozuk olsa bile)
+            let seal = if block.hash == expected {
+                self.consensus.verify_seal(block)
+            } else {
+                self.consensus.verify_seal(&Block { hash: expected, ..block.clone() })
+            };
+            if let Err(err) = seal {
+                report.push(i, ViolationKind::InvalidSeal(err));
+            }
+
+            // Genesis bloğun zorluğu zincirin başlangıç zorluğudur, sonrakiler uzlaşma motorunun takvimine uyar
+            let expected_bits = self.consensus.expected_bits(self.bits, &self.blocks[..i]);
+            if block.bits != expected_bits {
+                report.push(i, ViolationKind::WrongDifficulty { expected: expected_bits, found: block.bits });
+            }
+
+            if block.merkle_root != block.calculate_merkle_root() {
+                report.push(i, ViolationKind::MerkleRootMismatch);
+            }
+
+            // Defter ilk hatadan sonra anlamını yitirir (sonraki her blok aynı hatadan etkilenir); bu yüzden orada bırakılır
+            if let Some(ledger) = &mut utxos
+                && let Err(err) = ledger.apply_block(&block.transactions, i as u64)
+            {
+                report.push(i, ViolationKind::Ledger(err));
+                utxos = None;
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::{bits_to_target, hash_meets_target};
+    use crate::tests::{chain_with, mine};
+
+    fn kinds(report: &ValidationReport) -> Vec<(usize, String)> {
+        report
+            .violations
+            .iter()
+            .map(|violation| (violation.index, format!("{:?}", violation.kind)))
+            .collect()
+    }
+
+    #[test]
+    fn valid_chain_has_no_violations() {
+        let report = chain_with(4, "miner").validate();
+        assert!(report.is_valid());
+        assert_eq!(report.to_string(), "chain is valid");
+    }
+
+    #[test]
+    fn reports_index_link_and_timestamp_violations() {
+        let mut blockchain = chain_with(4, "miner");
+        let block = &mut blockchain.blocks[3];
+        block.index = 7;
+        block.previous_block_hash = [0xab; 32];
+        block.timestamp = 0;
+        mine(block); // Hash ve iş geçerli kalır, sadece başlık kuralları çiğnenir
+
+        let kinds = kinds(&blockchain.validate());
+        assert_eq!(kinds.len(), 3);
+        assert!(kinds.iter().all(|(index, _)| *index == 3));
+        assert!(kinds[0].1.starts_with("BadIndex"));
+        assert!(kinds[1].1.starts_with("BrokenLink"));
+        assert!(kinds[2].1.starts_with("NonMonotonicTimestamp"));
+    }
+
+    #[test]
+    fn reports_genesis_hash_and_work_violations() {
+        let mut blockchain = chain_with(3, "miner");
+        blockchain.blocks[0].previous_block_hash = [0x01; 32];
+        mine(&mut blockchain.blocks[0]); // Genesis'in hash'i değiştiği için sonraki bloğun bağlantısı da kopar
+        // Son bloğun nonce'u hedefi sağlamayan bir değere çekilir; saklanan hash de artık başlıkla uyuşmaz
+        let block = &mut blockchain.blocks[2];
+        while hash_meets_target(&block.calculate_hash(), &bits_to_target(block.bits)) {
+            block.nonce += 1;
+        }
+
+        let report = blockchain.validate();
+        let kinds = kinds(&report);
+        assert!(kinds[0].0 == 0 && kinds[0].1.starts_with("BadGenesis"));
+        assert!(kinds[1].0 == 1 && kinds[1].1.starts_with("BrokenLink"));
+        assert!(kinds[2].0 == 2 && kinds[2].1.starts_with("BadHash"));
+        assert!(kinds[3].0 == 2 && kinds[3].1.starts_with("InvalidSeal(InsufficientWork"));
+        assert_eq!(kinds.len(), 4);
+        assert!(report.to_string().starts_with("4 violation(s) found\n  block 0: genesis block"));
+    }
+}
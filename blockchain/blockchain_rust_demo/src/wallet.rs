@@ -0,0 +1,359 @@
+// Cüzdan: anahtar çiftleri, adresler ve şifreli anahtar deposu (keystore)
+// Adres, açık anahtarın HASH160'ı (RIPEMD160(SHA256(açık anahtar))) üzerinden Base58Check ile kodlanır:
+//   Base58(sürüm byte'ı | 20 byte hash | çift SHA256'nın ilk 4 byte'ı)
+// Sağlama toplamı (checksum) sayesinde yanlış yazılmış bir adres ödeme yapılmadan önce fark edilir
+//
+// Gizli anahtarlar diske açık yazılmaz: parola PBKDF2-HMAC-SHA256 ile bir anahtara çevrilir ve
+// gizli anahtar ChaCha20-Poly1305 ile şifrelenir; adresler ise parola gerekmeden listelenebilsin diye açık saklanır
+
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::consensus::Consensus;
+use crate::mempool::MempoolError;
+use crate::script::{hash160, Script};
+use crate::transaction::{Transaction, TxOutput};
+use crate::{from_hex, to_hex, Blockchain};
+
+// Adreslerin sürüm byte'ı (Bitcoin'deki P2PKH adresleri gibi "1" ile başlarlar)
+const ADDRESS_VERSION: u8 = 0x00;
+
+// Betik adreslerinin sürüm byte'ı (Bitcoin'deki P2SH adresleri gibi "3" ile başlarlar)
+const SCRIPT_ADDRESS_VERSION: u8 = 0x05;
+
+// Bitcoin'in Base58 alfabesi: birbirine benzeyen 0, O, I ve l karakterleri yoktur
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Paroladan anahtar türetilirken yapılan PBKDF2 tekrar sayısı (kaba kuvvet denemelerini yavaşlatır)
+pub const DEFAULT_KDF_ITERATIONS: u32 = 100_000;
+
+// Cüzdan işlemlerinde oluşabilecek hatalar
+#[derive(Debug)]
+pub enum WalletError {
+    Io(io::Error),     // Dosya okunamadı ya da yazılamadı
+    Malformed(String), // Keystore dosyası çözülemedi
+    DuplicateName,     // Aynı isimde cüzdan zaten var
+    NotFound,          // Bu isimde cüzdan yok
+    WrongPassphrase,   // Parola yanlış (ya da şifreli veri bozulmuş)
+}
+
+impl Display for WalletError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WalletError::Io(err) => write!(f, "I/O error: {}", err),
+            WalletError::Malformed(reason) => write!(f, "malformed keystore: {}", reason),
+            WalletError::DuplicateName => write!(f, "a wallet with that name already exists"),
+            WalletError::NotFound => write!(f, "no wallet with that name"),
+            WalletError::WrongPassphrase => write!(f, "wrong passphrase"),
+        }
+    }
+}
+
+impl std::error::Error for WalletError {}
+
+impl From<io::Error> for WalletError {
+    fn from(err: io::Error) -> Self {
+        WalletError::Io(err)
+    }
+}
+
+// Bir adrese düz (betiksiz) ödeme yapılamamasının nedeni
+#[derive(Debug, PartialEq, Eq)]
+pub enum AddressError {
+    Invalid,       // Base58Check çözülemedi, sağlama toplamı tutmuyor ya da sürüm byte'ı bilinmiyor
+    ScriptAddress, // Betik adresi: betiksiz çıktı bu adrese yazılırsa hiçbir anahtar onu harcayamaz
+}
+
+impl Display for AddressError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressError::Invalid => write!(f, "invalid address (checksum mismatch)"),
+            AddressError::ScriptAddress => {
+                write!(f, "script address; coins sent to it without its locking script could never be spent")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+// Açık anahtardan adres türetir
+pub fn address_of(public_key: &VerifyingKey) -> String {
+    let mut payload = vec![ADDRESS_VERSION];
+    payload.extend_from_slice(&hash160(public_key.as_bytes()));
+    base58check_encode(&payload)
+}
+
+// Kilitleme betiğinin adresi: betiğin ikili kodlamasının HASH160'ı
+// Betikli çıktılar bu adrese yazılır; bakiyesi ve harcanmamış çıktıları diğer adresler gibi sorgulanabilir
+pub fn script_address(script: &Script) -> String {
+    let mut payload = vec![SCRIPT_ADDRESS_VERSION];
+    payload.extend_from_slice(&hash160(&script.encode()));
+    base58check_encode(&payload)
+}
+
+// Adrese düz ödeme yapılabilir mi? Adres, sağlama toplamı tutan 21 byte'lık bir Base58Check değeri olmalıdır
+// Betik adresleri de geçerli adreslerdir, ama onlara sadece kilitleme betiğiyle birlikte ödeme yapılabilir
+pub fn check_payment_address(address: &str) -> Result<(), AddressError> {
+    let payload = base58check_decode(address).filter(|payload| payload.len() == 21).ok_or(AddressError::Invalid)?;
+    match payload[0] {
+        ADDRESS_VERSION => Ok(()),
+        SCRIPT_ADDRESS_VERSION => Err(AddressError::ScriptAddress),
+        _ => Err(AddressError::Invalid),
+    }
+}
+
+// Verinin sonuna çift SHA256'nın ilk 4 byte'ını ekleyip Base58 ile kodlar
+fn base58check_encode(payload: &[u8]) -> String {
+    let mut bytes = payload.to_vec();
+    bytes.extend_from_slice(&checksum(payload));
+    base58_encode(&bytes)
+}
+
+// Base58 çözer ve sağlama toplamını kontrol eder; geçerliyse sağlama toplamı çıkarılmış veriyi döndürür
+fn base58check_decode(encoded: &str) -> Option<Vec<u8>> {
+    let bytes = base58_decode(encoded)?;
+    let split = bytes.len().checked_sub(4)?;
+    let (payload, check) = bytes.split_at(split);
+    (checksum(payload) == check).then(|| payload.to_vec())
+}
+
+fn checksum(payload: &[u8]) -> [u8; 4] {
+    let digest = Sha256::digest(Sha256::digest(payload));
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+// Byte dizisini büyük bir sayı gibi 58 tabanına çevirir; baştaki her sıfır byte bir "1" olarak yazılır
+fn base58_encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    let mut digits: Vec<u8> = Vec::new(); // 58 tabanındaki basamaklar, en düşük basamak önce
+    for &byte in &bytes[zeros..] {
+        let mut carry = byte as u32;
+        for digit in &mut digits {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    std::iter::repeat_n('1', zeros)
+        .chain(digits.iter().rev().map(|&digit| BASE58_ALPHABET[digit as usize] as char))
+        .collect()
+}
+
+// Base58 metnini byte dizisine çevirir (alfabede olmayan karakter varsa None)
+fn base58_decode(encoded: &str) -> Option<Vec<u8>> {
+    let zeros = encoded.bytes().take_while(|&c| c == b'1').count();
+    let mut bytes: Vec<u8> = Vec::new(); // En düşük byte önce
+    for c in encoded.bytes().skip(zeros) {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in &mut bytes {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let mut decoded = vec![0u8; zeros];
+    decoded.extend(bytes.iter().rev());
+    Some(decoded)
+}
+
+// Keystore'daki bir cüzdan kaydı; gizli anahtar sadece şifreli haliyle saklanır
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WalletEntry {
+    pub name: String,
+    pub address: String,
+    salt: String,       // PBKDF2 tuzu (hex); aynı parola her cüzdanda farklı bir anahtar üretir
+    iterations: u32,    // PBKDF2 tekrar sayısı
+    nonce: String,      // ChaCha20-Poly1305 nonce'u (hex)
+    ciphertext: String, // Şifrelenmiş gizli anahtar ve doğrulama etiketi (hex)
+}
+
+// Kilidi açılmış bir cüzdan: işlemleri imzalayabilir
+pub struct Wallet {
+    pub name: String,
+    pub address: String,
+    pub key: SigningKey,
+}
+
+// Cüzdanların saklandığı JSON dosyası
+pub struct Keystore {
+    path: PathBuf,
+    wallets: Vec<WalletEntry>,
+    pub iterations: u32, // Yeni cüzdanlar için PBKDF2 tekrar sayısı
+}
+
+impl Keystore {
+    // Keystore dosyasını açar; dosya yoksa boş bir keystore döner (ilk cüzdan oluşturulunca yazılır)
+    pub fn open(path: impl Into<PathBuf>) -> Result<Keystore, WalletError> {
+        let path = path.into();
+        let wallets = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|err| WalletError::Malformed(err.to_string()))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Keystore { path, wallets, iterations: DEFAULT_KDF_ITERATIONS })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn wallets(&self) -> &[WalletEntry] {
+        &self.wallets
+    }
+
+    // Yeni bir anahtar çifti üretir, parolayla şifreleyip dosyaya yazar
+    pub fn create(&mut self, name: &str, passphrase: &str) -> Result<Wallet, WalletError> {
+        if self.wallets.iter().any(|entry| entry.name == name) {
+            return Err(WalletError::DuplicateName);
+        }
+        let key = SigningKey::generate(&mut OsRng);
+        let address = address_of(&key.verifying_key());
+
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+        let cipher = cipher(passphrase, &salt, self.iterations);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), key.to_bytes().as_slice())
+            .expect("Encrypting a 32-byte key cannot fail");
+
+        self.wallets.push(WalletEntry {
+            name: name.to_string(),
+            address: address.clone(),
+            salt: to_hex(&salt),
+            iterations: self.iterations,
+            nonce: to_hex(&nonce),
+            ciphertext: to_hex(&ciphertext),
+        });
+        if let Err(err) = self.save() {
+            self.wallets.pop();
+            return Err(err);
+        }
+        Ok(Wallet { name: name.to_string(), address, key })
+    }
+
+    // Cüzdanın gizli anahtarını parolayla çözer
+    pub fn unlock(&self, name: &str, passphrase: &str) -> Result<Wallet, WalletError> {
+        let entry = self.wallets.iter().find(|entry| entry.name == name).ok_or(WalletError::NotFound)?;
+        let malformed = || WalletError::Malformed(format!("wallet {} has invalid key material", name));
+        let salt = from_hex(&entry.salt).ok_or_else(malformed)?;
+        let nonce = from_hex(&entry.nonce).filter(|nonce| nonce.len() == 12).ok_or_else(malformed)?;
+        let ciphertext = from_hex(&entry.ciphertext).ok_or_else(malformed)?;
+
+        // Yanlış parola farklı bir anahtar üretir; doğrulama etiketi tutmadığı için çözme başarısız olur
+        let secret = cipher(passphrase, &salt, entry.iterations)
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| WalletError::WrongPassphrase)?;
+        let secret = <[u8; 32]>::try_from(secret).map_err(|_| malformed())?;
+        let key = SigningKey::from_bytes(&secret);
+        // Dosyada adres elle değiştirilmişse anahtarla uyuşmaz
+        if address_of(&key.verifying_key()) != entry.address {
+            return Err(malformed());
+        }
+        Ok(Wallet { name: entry.name.clone(), address: entry.address.clone(), key })
+    }
+
+    // Önce geçici dosyaya yazıp sonra yeniden adlandırır; yazma yarıda kalırsa eski keystore bozulmaz
+    fn save(&self) -> Result<(), WalletError> {
+        let contents = serde_json::to_string_pretty(&self.wallets).map_err(|err| WalletError::Malformed(err.to_string()))?;
+        let temporary = self.path.with_extension("tmp");
+        fs::write(&temporary, contents)?;
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+}
+
+impl<C: Consensus> Blockchain<C> {
+    // Ödemeleri anahtarın çıktılarından karşılayan bir işlem hazırlar, imzalar ve havuza ekler
+    // Havuzda harcanmış çıktılar tekrar seçilmesin diye işlem bekleyen durum üzerinden hazırlanır
+    pub fn send(&mut self, key: &SigningKey, payments: Vec<TxOutput>, fee: u64) -> Result<(Transaction, u64), MempoolError> {
+        let transaction = self.mempool.pending_utxos(&self.utxos).build_transaction(key, payments, fee)?;
+        let fee = self.submit_transaction(transaction.clone())?;
+        Ok((transaction, fee))
+    }
+}
+
+// Paroladan şifreleme anahtarı türetir
+fn cipher(passphrase: &str, salt: &[u8], iterations: u32) -> ChaCha20Poly1305 {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    ChaCha20Poly1305::new(Key::from_slice(&key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::temp_path;
+
+    fn keystore(name: &str) -> Keystore {
+        let mut keystore = Keystore::open(temp_path(&format!("keystore-{}.json", name))).unwrap();
+        keystore.iterations = 1_000; // Testler hızlı çalışsın
+        keystore
+    }
+
+    #[test]
+    fn base58_matches_known_vectors() {
+        assert_eq!(base58_encode(b"hello world"), "StV1DL6CwTryKyV");
+        assert_eq!(base58_encode(&[0, 0, 1]), "112");
+        assert_eq!(base58_decode("StV1DL6CwTryKyV").unwrap(), b"hello world");
+        assert_eq!(base58_decode("112").unwrap(), vec![0, 0, 1]);
+        assert!(base58_decode("0OIl").is_none());
+    }
+
+    #[test]
+    fn addresses_carry_a_checksum() {
+        let address = address_of(&SigningKey::generate(&mut OsRng).verifying_key());
+        assert!(address.starts_with('1'));
+        assert_eq!(check_payment_address(&address), Ok(()));
+        // Tek bir karakter değişince sağlama toplamı tutmaz
+        let mut typo: Vec<char> = address.chars().collect();
+        typo[5] = if typo[5] == 'z' { 'y' } else { 'z' };
+        assert_eq!(check_payment_address(&typo.into_iter().collect::<String>()), Err(AddressError::Invalid));
+        assert_eq!(check_payment_address("bob"), Err(AddressError::Invalid));
+        // Betik adresi geçerlidir ama düz ödeme kabul etmez
+        let script = script_address(&Script::timelock(1, &SigningKey::generate(&mut OsRng).verifying_key()));
+        assert!(script.starts_with('3'));
+        assert_eq!(check_payment_address(&script), Err(AddressError::ScriptAddress));
+    }
+
+    #[test]
+    fn keystore_round_trips_and_rejects_wrong_passphrase() {
+        let mut keystore = keystore("round-trip");
+        let created = keystore.create("alice", "correct horse").unwrap();
+        assert!(matches!(keystore.create("alice", "other"), Err(WalletError::DuplicateName)));
+
+        // Gizli anahtar dosyada açık halde bulunmaz
+        let contents = fs::read_to_string(keystore.path()).unwrap();
+        assert!(!contents.contains(&to_hex(&created.key.to_bytes())));
+
+        let reopened = Keystore::open(keystore.path()).unwrap();
+        assert_eq!(reopened.wallets().len(), 1);
+        let unlocked = reopened.unlock("alice", "correct horse").unwrap();
+        assert_eq!(unlocked.key.to_bytes(), created.key.to_bytes());
+        assert_eq!(unlocked.address, created.address);
+        assert!(matches!(reopened.unlock("alice", "wrong"), Err(WalletError::WrongPassphrase)));
+        assert!(matches!(reopened.unlock("bob", "correct horse"), Err(WalletError::NotFound)));
+
+        let _ = fs::remove_file(keystore.path());
+    }
+}
@@ -0,0 +1,322 @@
+// Minimal HTTP/1.1 request parsing and response writing
+// Minimal HTTP/1.1 istek ayrıştırma ve yanıt yazma
+//
+// A request looks like this on the wire:
+// Bir istek bağlantı üzerinde şöyle görünür:
+//
+//   POST /echo HTTP/1.1\r\n          <- request line / istek satırı
+//   Host: 127.0.0.1\r\n              <- headers / başlıklar
+//   Content-Length: 5\r\n
+//   \r\n                             <- empty line ends the headers / boş satır başlıkları bitirir
+//   hello                            <- body (exactly Content-Length bytes) / gövde
+
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+
+// Limits that protect the server from clients sending endless data
+// Sunucuyu sonu gelmeyen veri gönderen istemcilere karşı koruyan sınırlar
+pub const MAX_HEAD_SIZE: usize = 8 * 1024; // Request line + headers / İstek satırı + başlıklar
+pub const MAX_BODY_SIZE: usize = 1024 * 1024; // Body / Gövde
+
+// A parsed HTTP request
+// Ayrıştırılmış bir HTTP isteği
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,                 // GET, POST, ...
+    pub path: String,                   // /echo
+    pub version: String,                // HTTP/1.1
+    pub headers: Vec<(String, String)>, // Header names keep their original case / Başlık adları yazıldığı gibi kalır
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    // Header names are case-insensitive in HTTP, so the lookup ignores case
+    // HTTP'de başlık adları büyük/küçük harfe duyarsızdır, bu yüzden arama harf farkını yok sayar
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    // Should the connection stay open after this request?
+    // Bu istekten sonra bağlantı açık kalmalı mı?
+    // HTTP/1.1 keeps connections open unless the client says "Connection: close";
+    // HTTP/1.0 closes them unless the client says "Connection: keep-alive"
+    // HTTP/1.1 istemci "Connection: close" demedikçe bağlantıyı açık tutar;
+    // HTTP/1.0 ise istemci "Connection: keep-alive" demedikçe kapatır
+    pub fn keep_alive(&self) -> bool {
+        let connection = self.header("Connection").unwrap_or("");
+        let has = |token: &str| connection.split(',').any(|part| part.trim().eq_ignore_ascii_case(token));
+        if self.version == "HTTP/1.0" {
+            has("keep-alive")
+        } else {
+            !has("close")
+        }
+    }
+}
+
+// Why reading a request failed
+// İsteğin okunmasının neden başarısız olduğu
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),      // The connection failed / Bağlantı koptu
+    Closed,             // The client closed the connection before sending a request / İstemci istek göndermeden kapattı
+    BadRequest(String), // The request is not valid HTTP / İstek geçerli HTTP değil
+    HeadTooLarge,       // Request line and headers exceed MAX_HEAD_SIZE / Başlıklar çok büyük
+    BodyTooLarge,       // Content-Length exceeds MAX_BODY_SIZE / Gövde çok büyük
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io(err) => write!(f, "I/O error: {}", err),
+            ParseError::Closed => write!(f, "connection closed"),
+            ParseError::BadRequest(reason) => write!(f, "bad request: {}", reason),
+            ParseError::HeadTooLarge => write!(f, "request head exceeds {} bytes", MAX_HEAD_SIZE),
+            ParseError::BodyTooLarge => write!(f, "request body exceeds {} bytes", MAX_BODY_SIZE),
+        }
+    }
+}
+
+impl From<io::Error> for ParseError {
+    fn from(err: io::Error) -> Self {
+        ParseError::Io(err)
+    }
+}
+
+impl ParseError {
+    // The error response sent back to the client (None when there is nobody to answer)
+    // İstemciye geri gönderilecek hata yanıtı (cevap verilecek kimse yoksa None)
+    pub fn response(&self) -> Option<Response> {
+        match self {
+            // The read timeout expired in the middle of a request: the client is too slow
+            // Okuma zaman aşımı bir isteğin ortasında doldu: istemci çok yavaş
+            ParseError::Io(err) if is_timeout(err) => Some(Response::text(408, "timed out reading the request")),
+            ParseError::Io(_) | ParseError::Closed => None,
+            ParseError::BadRequest(reason) => Some(Response::text(400, reason)),
+            ParseError::HeadTooLarge => Some(Response::text(431, &self.to_string())),
+            ParseError::BodyTooLarge => Some(Response::text(413, &self.to_string())),
+        }
+    }
+}
+
+// A read that hits set_read_timeout fails with WouldBlock on Unix and TimedOut on Windows
+// set_read_timeout süresini aşan okuma Unix'te WouldBlock, Windows'ta TimedOut hatası verir
+pub fn is_timeout(err: &io::Error) -> bool {
+    matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut)
+}
+
+// Reads one request from the stream
+// Akıştan bir istek okur
+// BufReader keeps the bytes it has read ahead, so a request split across many TCP reads
+// (or several requests arriving in one read) is handled correctly
+// BufReader önden okuduğu byte'ları saklar; böylece birçok TCP okumasına bölünmüş bir istek
+// (ya da tek okumada gelen birden fazla istek) doğru işlenir
+pub fn read_request(reader: &mut impl BufRead) -> Result<Request, ParseError> {
+    let mut head_size = 0;
+
+    let request_line = read_line(reader, &mut head_size)?.ok_or(ParseError::Closed)?;
+    let mut parts = request_line.split(' ');
+    let (Some(method), Some(path), Some(version), None) = (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(ParseError::BadRequest(format!("malformed request line: {}", request_line)));
+    };
+    if method.is_empty() || !path.starts_with('/') || !version.starts_with("HTTP/1.") {
+        return Err(ParseError::BadRequest(format!("malformed request line: {}", request_line)));
+    }
+
+    // Headers continue until the first empty line
+    // Başlıklar ilk boş satıra kadar devam eder
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(reader, &mut head_size)?
+            .ok_or_else(|| ParseError::BadRequest(String::from("connection closed inside headers")))?;
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(ParseError::BadRequest(format!("malformed header: {}", line)));
+        };
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+
+    let mut request = Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        version: version.to_string(),
+        headers,
+        body: Vec::new(),
+    };
+
+    // The body is exactly Content-Length bytes; read_exact keeps reading until all of them arrive
+    // Gövde tam olarak Content-Length byte'tır; read_exact hepsi gelene kadar okumaya devam eder
+    if request.header("Transfer-Encoding").is_some() {
+        return Err(ParseError::BadRequest(String::from("Transfer-Encoding is not supported")));
+    }
+    // Two different Content-Length values make the end of the body ambiguous; a proxy in front of the
+    // server could pick the other one and smuggle a second request inside the body (RFC 9112 section 6.3)
+    // Birbirinden farklı iki Content-Length değeri gövdenin nerede bittiğini belirsizleştirir; önümüzdeki bir
+    // proxy diğerini seçip gövdenin içinde ikinci bir istek kaçırabilir (RFC 9112 bölüm 6.3)
+    let mut lengths = request.headers.iter().filter(|(name, _)| name.eq_ignore_ascii_case("Content-Length"));
+    if let Some((_, first)) = lengths.next()
+        && lengths.any(|(_, other)| other != first)
+    {
+        return Err(ParseError::BadRequest(String::from("conflicting Content-Length headers")));
+    }
+    if let Some(length) = request.header("Content-Length") {
+        let length: usize = length
+            .parse()
+            .map_err(|_| ParseError::BadRequest(format!("invalid Content-Length: {}", length)))?;
+        if length > MAX_BODY_SIZE {
+            return Err(ParseError::BodyTooLarge);
+        }
+        request.body = vec![0; length];
+        reader.read_exact(&mut request.body).map_err(|err| match err.kind() {
+            io::ErrorKind::UnexpectedEof => ParseError::BadRequest(String::from("body shorter than Content-Length")),
+            _ => ParseError::Io(err),
+        })?;
+    }
+    Ok(request)
+}
+
+// Reads one CRLF-terminated line without the line ending (None at end of stream)
+// Satır sonu olmadan CRLF ile biten bir satır okur (akış bittiyse None)
+fn read_line(reader: &mut impl BufRead, head_size: &mut usize) -> Result<Option<String>, ParseError> {
+    // take() stops a client from sending a single endless line
+    // take(), istemcinin sonu gelmeyen tek bir satır göndermesini engeller
+    let remaining = (MAX_HEAD_SIZE - *head_size) as u64;
+    let mut line = Vec::new();
+    reader.take(remaining + 1).read_until(b'\n', &mut line)?;
+    *head_size += line.len();
+    if *head_size > MAX_HEAD_SIZE {
+        return Err(ParseError::HeadTooLarge);
+    }
+    if line.is_empty() {
+        return Ok(None);
+    }
+    if !line.ends_with(b"\r\n") {
+        return Err(ParseError::BadRequest(String::from("line is not terminated by CRLF")));
+    }
+    line.truncate(line.len() - 2);
+    String::from_utf8(line)
+        .map(Some)
+        .map_err(|_| ParseError::BadRequest(String::from("request head is not valid UTF-8")))
+}
+
+// An HTTP response
+// Bir HTTP yanıtı
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,                    // 200, 404, ...
+    pub headers: Vec<(String, String)>, // Content-Length is added when writing / Content-Length yazarken eklenir
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn new(status: u16, content_type: &str, body: Vec<u8>) -> Response {
+        Response { status, headers: vec![(String::from("Content-Type"), content_type.to_string())], body }
+    }
+
+    // Plain text response
+    // Düz metin yanıtı
+    pub fn text(status: u16, body: &str) -> Response {
+        Response::new(status, "text/plain; charset=utf-8", body.as_bytes().to_vec())
+    }
+
+    // Writes the status line, headers and body with proper HTTP framing
+    // Durum satırını, başlıkları ve gövdeyi doğru HTTP biçiminde yazar
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason_phrase(self.status));
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!("Content-Length: {}\r\n\r\n", self.body.len()));
+        writer.write_all(head.as_bytes())?;
+        writer.write_all(&self.body)?;
+        writer.flush()
+    }
+}
+
+// The standard text that follows the status code
+// Durum kodunu izleyen standart metin
+pub fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    // A reader that returns at most a few bytes per read, like a slow network
+    // Yavaş bir ağ gibi her okumada en fazla birkaç byte döndüren okuyucu
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(3);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn parses_a_request_split_across_reads() {
+        let raw = b"POST /echo HTTP/1.1\r\nHost: localhost\r\ncontent-length: 11\r\n\r\nhello world";
+        let request = read_request(&mut BufReader::new(Trickle(raw))).unwrap();
+        assert_eq!((request.method.as_str(), request.path.as_str()), ("POST", "/echo"));
+        assert_eq!(request.header("Content-Length"), Some("11"));
+        assert_eq!(request.body, b"hello world");
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        let parse = |raw: &[u8]| read_request(&mut BufReader::new(raw));
+        assert!(matches!(parse(b""), Err(ParseError::Closed)));
+        assert!(matches!(parse(b"GET /\r\n\r\n"), Err(ParseError::BadRequest(_))));
+        assert!(matches!(parse(b"GET / HTTP/1.1\r\nNoColon\r\n\r\n"), Err(ParseError::BadRequest(_))));
+        assert!(matches!(parse(b"POST / HTTP/1.1\r\nContent-Length: 9\r\n\r\nshort"), Err(ParseError::BadRequest(_))));
+        let conflicting = b"POST / HTTP/1.1\r\nContent-Length: 0\r\ncontent-length: 5\r\n\r\nhello";
+        assert!(matches!(parse(conflicting), Err(ParseError::BadRequest(_))));
+        let repeated = parse(b"POST / HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 5\r\n\r\nhello").unwrap();
+        assert_eq!(repeated.body, b"hello");
+        let huge = format!("GET / HTTP/1.1\r\nX: {}\r\n\r\n", "a".repeat(MAX_HEAD_SIZE));
+        assert!(matches!(parse(huge.as_bytes()), Err(ParseError::HeadTooLarge)));
+        let big_body = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY_SIZE + 1);
+        assert!(matches!(parse(big_body.as_bytes()), Err(ParseError::BodyTooLarge)));
+    }
+
+    #[test]
+    fn keep_alive_depends_on_version_and_connection_header() {
+        let parse = |raw: &str| read_request(&mut BufReader::new(raw.as_bytes())).unwrap();
+        assert!(parse("GET / HTTP/1.1\r\n\r\n").keep_alive());
+        assert!(!parse("GET / HTTP/1.1\r\nConnection: Close\r\n\r\n").keep_alive());
+        assert!(!parse("GET / HTTP/1.0\r\n\r\n").keep_alive());
+        assert!(parse("GET / HTTP/1.0\r\nConnection: keep-alive\r\n\r\n").keep_alive());
+    }
+
+    #[test]
+    fn writes_status_line_headers_and_body() {
+        let mut out = Vec::new();
+        Response::text(404, "nope").write_to(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: 4\r\n\r\nnope"
+        );
+    }
+}
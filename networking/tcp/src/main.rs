@@ -5,36 +5,101 @@ Modül dosyasını tanıtmak	mod tcp;
 Fonksiyonu içeri almak	use tcp::handle_client;
 */
 
+mod http;
+mod pool;
 mod tcp;
 
-use std::net::TcpListener;
-use tcp::handle_client; // BU SATIR ÖNEMLİ ; Böylece handle_client fonksiyonunu kullanabiliriz
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use http::{Request, Response};
+use pool::ThreadPool;
+use tcp::{handle_client, reject_busy, Router}; // BU SATIR ÖNEMLİ ; Böylece handle_client fonksiyonunu kullanabiliriz
+
+const ADDRESS: &str = "127.0.0.1:8080";
+
+// Number of worker threads and how many connections may wait for a free worker
+// İşçi iş parçacığı sayısı ve boşta işçi bekleyebilecek bağlantı sayısı
+const WORKERS: usize = 4;
+const QUEUE_CAPACITY: usize = 16;
+
+// Set by POST /shutdown; the accept loop stops when it sees it
+// POST /shutdown ile işaretlenir; kabul döngüsü bunu görünce durur
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
+// Handlers registered on the router
+// Router'a kaydedilen handler'lar
+fn hello(_request: &Request) -> Response {
+    Response::text(200, "Hello, Client")
+}
+
+fn health(_request: &Request) -> Response {
+    Response::text(200, "OK")
+}
+
+// Sends the request body back with the same content type
+// İstek gövdesini aynı içerik türüyle geri gönderir
+fn echo(request: &Request) -> Response {
+    let content_type = request.header("Content-Type").unwrap_or("application/octet-stream");
+    Response::new(200, content_type, request.body.clone())
+}
+
+// Asks the server to stop accepting connections
+// Sunucudan bağlantı kabul etmeyi bırakmasını ister
+fn shutdown(_request: &Request) -> Response {
+    SHUTDOWN.store(true, Ordering::SeqCst);
+    // accept() blocks until the next client, so the handler connects once to wake the listener up
+    // accept() bir sonraki istemciye kadar bekler; bu yüzden handler dinleyiciyi uyandırmak için bir kez bağlanır
+    let _ = TcpStream::connect(ADDRESS);
+    Response::text(200, "Shutting down")
+}
+
 fn main() {
+    // Every connection thread uses the same routes, so the router is shared with Arc
+    // Her bağlantı iş parçacığı aynı rotaları kullanır, bu yüzden router Arc ile paylaşılır
+    let router = Arc::new(
+        Router::new()
+            .route("GET", "/", hello)
+            .route("GET", "/health", health)
+            .route("POST", "/echo", echo)
+            .route("POST", "/shutdown", shutdown),
+    );
+    let pool = ThreadPool::new(WORKERS, QUEUE_CAPACITY);
+
     // This line creates a TCP listener that binds to the specified address and port
     // Bu satır, belirtilen adres ve bağlantı noktasına bağlanan bir TCP dinleyici oluşturur
-    let listener = TcpListener::bind("127.0.0.1:8080").
+    let listener = TcpListener::bind(ADDRESS).
     expect("Failed to bind to address");
-    println!("Server listening on port 8080");
+    println!("Server listening on {} with {} workers (POST /shutdown to stop)", ADDRESS, WORKERS);
 
     for stream in listener.incoming() {
-        // This line handles each incoming TCP connection in a separate thread
-        // Bu satır, her gelen TCP bağlantısını ayrı bir iş parçacığında işler
+        if SHUTDOWN.load(Ordering::SeqCst) {
+            break;
+        }
+        // This line hands each incoming TCP connection to a worker of the pool
+        // Bu satır, her gelen TCP bağlantısını havuzdaki bir işçiye verir
         match stream {
             Ok(stream) => {
             /*
-               std::thread::spawn yeni bir iş parçacığı (thread) başlatır.
-               Bu, programın aynı anda birden fazla işi paralel olarak yapmasını sağlar.
-               Örneğin, her istemci bağlantısı için ayrı bir iş parçacığı oluşturmak gibi.
+               pool.execute işi kuyruğa ekler; boşta olan ilk işçi onu çalıştırır.
+               std::thread::spawn'dan farkı: iş parçacıkları bir kez açılır ve tekrar tekrar kullanılır,
+               bu yüzden aynı anda çalışan iş parçacığı sayısı WORKERS ile sınırlıdır.
 
-               Closure `|| handle_client(stream)` ise handle_client fonksiyonunu çağırır.
+               Closure `move || handle_client(stream, &router)` ise handle_client fonksiyonunu çağırır.
+               `move`, stream'i ve router'ın kopyasını (Arc::clone) işçiye taşır.
                Closure, bir anonim fonksiyondur ve `||` ile tanımlanır.
-               Bu closure, yeni iş parçacığında çalıştırılacak işlemi temsil eder.
-               Burada `handle_client(stream)` fonksiyonu, istemci bağlantısını işlemek için çağrılır.
-            */
-                std::thread::spawn(|| handle_client(stream));
-
-
 
+               Kuyruk doluysa execute closure'ı geri verir (Err). Stream closure'ın içinde olduğu için
+               meşgul cevabı, önceden kopyalanan (try_clone) bağlantı üzerinden yazılır.
+            */
+                let busy = stream.try_clone();
+                let router = Arc::clone(&router);
+                if pool.execute(move || handle_client(stream, &router)).is_err() {
+                    match busy {
+                        Ok(busy) => reject_busy(busy),
+                        Err(e) => eprintln!("Failed to reject connection: {}", e),
+                    }
+                }
             }
             Err(e) => {
                 // eprintln ile hata mesajını standart hata akışına yazdırır (stderr)
@@ -42,4 +107,12 @@ fn main() {
             }
         }
     }
-}
\ No newline at end of file
+
+    // Dropping the pool lets the workers finish the connections they are serving and the queued ones
+    // Havuz düşürülünce işçiler üzerinde çalıştıkları ve kuyrukta bekleyen bağlantıları bitirir
+    // (an idle keep-alive connection ends at the latest after tcp::IDLE_TIMEOUT)
+    // (boşta bekleyen keep-alive bağlantısı en geç tcp::IDLE_TIMEOUT sonra biter)
+    println!("Stopped accepting connections, draining in-flight ones...");
+    drop(pool);
+    println!("Server stopped");
+}
@@ -0,0 +1,169 @@
+// A fixed-size thread pool with a bounded job queue
+// Sabit sayıda iş parçacığı ve sınırlı bir iş kuyruğu olan iş parçacığı havuzu (thread pool)
+//
+// Spawning a thread per connection lets a burst of clients create unlimited threads.
+// Her bağlantı için iş parçacığı açmak, ani bir istemci dalgasının sınırsız iş parçacığı açmasına yol açar.
+// The pool starts its workers once; jobs wait in a queue until a worker is free.
+// Havuz, işçileri (worker) bir kez başlatır; işler boşta bir işçi olana kadar kuyrukta bekler.
+// When the queue is full a new job is refused, so the caller can answer "busy" right away (backpressure).
+// Kuyruk doluysa yeni iş reddedilir; böylece çağıran hemen "meşgul" cevabı verebilir (geri basınç / backpressure).
+
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+
+// A job is a closure that runs once on a worker thread
+// İş, bir işçi iş parçacığında bir kez çalışan bir closure'dır
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+// State shared between the pool and its workers
+// Havuz ile işçileri arasında paylaşılan durum
+struct Queue {
+    jobs: VecDeque<Job>, // Jobs waiting for a worker / İşçi bekleyen işler
+    shutting_down: bool, // No new jobs are accepted / Yeni iş kabul edilmiyor
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    available: Condvar, // Wakes workers when a job arrives or the pool shuts down / İş gelince ya da kapanışta işçileri uyandırır
+}
+
+pub struct ThreadPool {
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+    capacity: usize, // Maximum number of waiting jobs / Bekleyebilecek en fazla iş sayısı
+}
+
+impl ThreadPool {
+    // Starts `size` worker threads; at most `capacity` jobs may wait for them
+    // `size` kadar işçi başlatır; onları en fazla `capacity` iş bekleyebilir
+    pub fn new(size: usize, capacity: usize) -> ThreadPool {
+        assert!(size > 0, "a thread pool needs at least one worker");
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue { jobs: VecDeque::new(), shutting_down: false }),
+            available: Condvar::new(),
+        });
+        let workers = (0..size)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || work(&shared))
+            })
+            .collect();
+        ThreadPool { shared, workers, capacity }
+    }
+
+    // Queues the job; gives it back when the queue is full so the caller can reject the work
+    // İşi kuyruğa ekler; kuyruk doluysa işi geri verir, böylece çağıran işi reddedebilir
+    pub fn execute<F>(&self, job: F) -> Result<(), F>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut queue = self.shared.queue.lock().unwrap();
+        if queue.shutting_down || queue.jobs.len() >= self.capacity {
+            return Err(job);
+        }
+        queue.jobs.push_back(Box::new(job));
+        self.shared.available.notify_one();
+        Ok(())
+    }
+}
+
+// Graceful shutdown: dropping the pool stops new jobs, lets the workers finish
+// the running and queued jobs, then waits for every worker to exit
+// Düzgün kapanış: havuz düşürülünce (drop) yeni iş alınmaz, işçiler çalışan ve
+// kuyruktaki işleri bitirir, sonra her işçinin çıkması beklenir
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.shared.queue.lock().unwrap().shutting_down = true;
+        self.shared.available.notify_all();
+        for worker in self.workers.drain(..) {
+            if worker.join().is_err() {
+                eprintln!("A worker thread panicked");
+            }
+        }
+    }
+}
+
+// The loop each worker runs: take a job, run it, repeat until the pool shuts down and the queue is empty
+// Her işçinin döngüsü: iş al, çalıştır; havuz kapanıp kuyruk boşalana kadar tekrarla
+fn work(shared: &Shared) {
+    loop {
+        let job = {
+            let mut queue = shared.queue.lock().unwrap();
+            loop {
+                if let Some(job) = queue.jobs.pop_front() {
+                    break job;
+                }
+                if queue.shutting_down {
+                    return;
+                }
+                // wait() releases the lock while sleeping and takes it back when woken
+                // wait(), uyurken kilidi bırakır ve uyanınca geri alır
+                queue = shared.available.wait(queue).unwrap();
+            }
+        };
+        // The lock is released before running the job, so other workers can take jobs meanwhile
+        // Kilit iş çalışmadan önce bırakılır; böylece diğer işçiler bu arada iş alabilir
+        // A panicking job must not kill the worker, otherwise the pool would shrink with every panic
+        // Panikleyen bir iş işçiyi öldürmemelidir; yoksa havuz her panikte bir işçi kaybederdi
+        if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+            eprintln!("A job panicked; the worker keeps running");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+
+    #[test]
+    fn full_queue_rejects_new_jobs() {
+        let pool = ThreadPool::new(1, 1);
+        // The only worker blocks until the test lets it go
+        // Tek işçi, test izin verene kadar bekler
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        assert!(pool
+            .execute(move || {
+                started_tx.send(()).unwrap();
+                release_rx.recv().unwrap();
+            })
+            .is_ok());
+        started_rx.recv().unwrap();
+        assert!(pool.execute(|| {}).is_ok()); // Waits in the queue / Kuyrukta bekler
+        assert!(pool.execute(|| {}).is_err()); // Queue is full / Kuyruk dolu
+        release_tx.send(()).unwrap();
+    }
+
+    #[test]
+    fn panicking_job_does_not_lose_the_worker() {
+        let pool = ThreadPool::new(1, 2);
+        for _ in 0..3 {
+            let (done_tx, done_rx) = mpsc::channel();
+            assert!(pool.execute(|| panic!("handler failed")).is_ok());
+            assert!(pool.execute(move || done_tx.send(()).unwrap()).is_ok());
+            // The only worker survived the panic and ran the next job
+            // Tek işçi panikten sağ çıktı ve sonraki işi çalıştırdı
+            done_rx.recv().unwrap();
+        }
+    }
+
+    #[test]
+    fn drop_finishes_queued_jobs() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::new(2, 10);
+        for _ in 0..10 {
+            let done = Arc::clone(&done);
+            let queued = pool.execute(move || {
+                thread::sleep(std::time::Duration::from_millis(5));
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+            assert!(queued.is_ok());
+        }
+        drop(pool);
+        assert_eq!(done.load(Ordering::SeqCst), 10);
+    }
+}
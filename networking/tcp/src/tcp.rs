@@ -3,46 +3,323 @@
 // io and net modules are used for input/output operations and network communication
 // io ve net modülleri giriş/çıkış işlemleri ve ağ iletişimi için kullanılır
 use std::net::TcpStream;
-use std::io::{Read, Write};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::time::{Duration, Instant};
 
+use crate::http::{is_timeout, read_request, Request, Response};
+
+// How long an open connection may wait for its next request before it is closed
+// Açık bir bağlantının bir sonraki isteğini kapatılmadan önce ne kadar bekleyebileceği
+// Kept short because an idle connection holds one of the pool's workers
+// Kısa tutulur, çünkü boşta bekleyen bağlantı havuzun işçilerinden birini meşgul eder
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// How long reading a started request may take in total (protects against very slow clients)
+// Başlamış bir isteğin okunmasının toplamda ne kadar sürebileceği (çok yavaş istemcilere karşı korur)
+pub const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+// How long writing a response may take in total (protects against clients that never read)
+// Bir yanıtın yazılmasının toplamda ne kadar sürebileceği (hiç okumayan istemcilere karşı korur)
+pub const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Maximum number of requests served on one connection before it is closed
+// Bir bağlantıda kapatılmadan önce cevaplanacak en fazla istek sayısı
+pub const MAX_REQUESTS_PER_CONNECTION: usize = 100;
+
+// A handler turns a request into a response
+// Bir handler (işleyici) isteği yanıta dönüştürür
+pub type Handler = fn(&Request) -> Response;
+
+// Router keeps the registered handlers and picks one by method and path
+// Router kayıtlı handler'ları tutar ve metoda ve yola göre birini seçer
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<(String, String, Handler)>, // (method, path, handler) / (metot, yol, işleyici)
+}
+
+impl Router {
+    pub fn new() -> Router {
+        Router::default()
+    }
+
+    // Registers a handler for a method and an exact path, e.g. route("GET", "/", hello)
+    // Bir metot ve tam yol için handler kaydeder, örnek: route("GET", "/", hello)
+    pub fn route(mut self, method: &str, path: &str, handler: Handler) -> Router {
+        self.routes.push((method.to_string(), path.to_string(), handler));
+        self
+    }
+
+    // Finds the handler for the request
+    // İsteğin handler'ını bulur
+    // If the path exists with another method the answer is 405, if the path is unknown it is 404
+    // Yol başka bir metotla kayıtlıysa cevap 405, yol hiç bilinmiyorsa 404 olur
+    pub fn handle(&self, request: &Request) -> Response {
+        // The query string (?a=b) is not part of the route
+        // Sorgu metni (?a=b) rotanın parçası değildir
+        let path = request.path.split('?').next().unwrap_or("");
+        let mut allowed = Vec::new();
+        for (method, route, handler) in &self.routes {
+            if route != path {
+                continue;
+            }
+            if *method == request.method {
+                return handler(request);
+            }
+            allowed.push(method.as_str());
+        }
+        if allowed.is_empty() {
+            return Response::text(404, &format!("No route for {}", path));
+        }
+        let mut response = Response::text(405, &format!("{} is not allowed on {}", request.method, path));
+        response.headers.push((String::from("Allow"), allowed.join(", ")));
+        response
+    }
+}
+
+// A view of the stream whose reads and writes must all finish before a deadline
+// Okumalarının ve yazmalarının hepsi bir son tarihten (deadline) önce bitmesi gereken akış görünümü
+// set_read_timeout / set_write_timeout limit a single read or write call, so a client sending
+// one byte just before each timeout could hold a worker forever; the deadline limits the total
+// set_read_timeout / set_write_timeout tek bir okuma ya da yazma çağrısını sınırlar; her zaman
+// aşımından hemen önce bir byte gönderen istemci işçiyi sonsuza kadar tutabilirdi; deadline toplamı sınırlar
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    until: Instant,
+}
+
+impl<'a> Deadline<'a> {
+    fn new(stream: &'a TcpStream, timeout: Duration) -> Deadline<'a> {
+        Deadline { stream, until: Instant::now() + timeout }
+    }
+
+    // Time left before the deadline; an expired deadline fails like a socket timeout
+    // Son tarihe kalan süre; süresi dolmuş deadline, soket zaman aşımı gibi hata verir
+    fn remaining(&self) -> io::Result<Duration> {
+        let remaining = self.until.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::from(io::ErrorKind::TimedOut));
+        }
+        Ok(remaining)
+    }
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream.set_read_timeout(Some(self.remaining()?))?;
+        self.stream.read(buf)
+    }
+}
+
+impl Write for Deadline<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.set_write_timeout(Some(self.remaining()?))?;
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
 
 // handle_client function is used to handle a single TCP connection
 // handle_client fonksiyonu, tek bir TCP bağlantısını işlemek için kullanılır
-pub fn handle_client(mut stream: TcpStream) {
-
-    // This is a buffer to temporarily store data read from the client
-    // Bu, istemciden okunan veriyi geçici olarak depolamak için bir tampon (buffer)
-    // It is a 1024-element array where each element is initialized to 0
-    // Bu, her elemanı 0 olarak başlatılmış 1024 elemanlı bir dizidir
-    let mut buffer = [0; 1024]; 
-
-    // This line reads data from the TCP stream and writes it into the buffer
-    // Bu satır, TCP akışından veri okur ve buffer dizisine yazar
-    // If the read operation fails, the program will panic with the given error message
-    // Eğer okuma işlemi başarısız olursa, program belirtilen hata mesajıyla durur
-    stream.read(&mut buffer).expect("Failed to read from client"); 
-
-    // Converts the byte array (buffer) into a UTF-8 string
-    // Byte dizisini (buffer) UTF-8 formatında bir metne dönüştürür
-    // If the byte array contains invalid UTF-8, it replaces invalid characters with �
-    // Eğer byte dizisi geçersiz UTF-8 içeriyorsa, geçersiz karakterleri � ile değiştirir
-    /*
-        -> lossy_utf8, bir byte dilimini UTF-8 metnine dönüştüren bir yöntemdir.
-        Geçersiz UTF-8 dizilerini � (U+FFFD) ile değiştirir
-        -> &buffer[..] ifadesi, buffer dizisinin tamamını alır
-     */
-    // At this point, `request` contains the data sent by the client as a readable string
-    // Bu noktada, `request` istemcinin gönderdiği veriyi okunabilir bir metin olarak içerir
-    let request = String::from_utf8_lossy(&buffer[..]);
-    println!("Received request: {}", request);
-
-    // Converts the response string into a byte array to send over the TCP connection
-    // Yanıt metnini TCP bağlantısı üzerinden göndermek için bir byte dizisine dönüştürür
-    let response = "Hello, Client".as_bytes(); 
-
-    // This line writes the response byte array back to the TCP stream
-    // Bu satır, yanıt byte dizisini TCP akışına geri yazar
-    stream.write(response).expect("Failed to write to response");
-    
-    
-}
\ No newline at end of file
+// The connection is persistent: requests are answered one after another until the client
+// asks to close, goes idle for too long or reaches MAX_REQUESTS_PER_CONNECTION
+// Bağlantı kalıcıdır (keep-alive): istemci kapatmak isteyene, çok uzun süre boşta kalana ya da
+// MAX_REQUESTS_PER_CONNECTION sınırına ulaşana kadar istekler sırayla cevaplanır
+pub fn handle_client(stream: TcpStream, router: &Router) {
+
+    // BufReader collects the bytes of the request across as many reads as needed
+    // BufReader, isteğin byte'larını gerektiği kadar okuma boyunca toplar
+    // (a single 1024-byte read may contain only part of a request, or nothing but headers)
+    // (tek bir 1024 byte'lık okuma isteğin sadece bir kısmını ya da sadece başlıkları içerebilir)
+    // The same reader is used for every request on the connection, so bytes of the next
+    // (pipelined) request that arrived early are kept for the next round of the loop
+    // Bağlantıdaki her istek için aynı okuyucu kullanılır; erken gelen bir sonraki (pipelined)
+    // isteğin byte'ları döngünün bir sonraki turuna saklanır
+    let mut reader = BufReader::new(Deadline::new(&stream, IDLE_TIMEOUT));
+
+    for served in 1..=MAX_REQUESTS_PER_CONNECTION {
+        // Wait for the first byte of the next request with the idle timeout
+        // Bir sonraki isteğin ilk byte'ı boşta bekleme süresiyle beklenir
+        *reader.get_mut() = Deadline::new(&stream, IDLE_TIMEOUT);
+        match reader.fill_buf() {
+            Ok([]) => return, // The client closed the connection / İstemci bağlantıyı kapattı
+            Ok(_) => {}
+            Err(err) => {
+                // An idle connection is closed quietly; it frees the worker for other clients
+                // Boşta kalan bağlantı sessizce kapatılır; işçi diğer istemciler için boşalır
+                if !is_timeout(&err) {
+                    eprintln!("Failed to read from client: {}", err);
+                }
+                return;
+            }
+        }
+
+        // Once a request has started, all of the rest must arrive within the read timeout
+        // İstek başladıktan sonra geri kalanının tamamı okuma zaman aşımı içinde gelmelidir
+        *reader.get_mut() = Deadline::new(&stream, READ_TIMEOUT);
+
+        // The request is parsed, then routed; malformed requests get an error response instead
+        // İstek ayrıştırılır, sonra yönlendirilir; bozuk isteklere bunun yerine hata yanıtı verilir
+        // After an error the position of the next request is unknown, so the connection is closed
+        // Hatadan sonra bir sonraki isteğin nerede başladığı bilinmez, bu yüzden bağlantı kapatılır
+        let (mut response, keep_alive) = match read_request(&mut reader) {
+            Ok(request) => {
+                println!("{} {} {}", request.method, request.path, request.version);
+                (router.handle(&request), request.keep_alive() && served < MAX_REQUESTS_PER_CONNECTION)
+            }
+            Err(err) => {
+                eprintln!("Failed to read request: {}", err);
+                match err.response() {
+                    Some(response) => (response, false),
+                    None => return, // Nobody to answer / Cevap verilecek kimse yok
+                }
+            }
+        };
+
+        // The Connection header tells the client whether it may send another request
+        // Connection başlığı, istemciye başka bir istek gönderip gönderemeyeceğini söyler
+        if keep_alive {
+            response.headers.push((String::from("Connection"), String::from("keep-alive")));
+            let remaining = MAX_REQUESTS_PER_CONNECTION - served;
+            let limits = format!("timeout={}, max={}", IDLE_TIMEOUT.as_secs(), remaining);
+            response.headers.push((String::from("Keep-Alive"), limits));
+        } else {
+            response.headers.push((String::from("Connection"), String::from("close")));
+        }
+
+        // This line writes the status line, headers and body back to the TCP stream
+        // Bu satır, durum satırını, başlıkları ve gövdeyi TCP akışına geri yazar
+        // A client that stops reading cannot block the worker longer than the write timeout
+        // Okumayı bırakan istemci işçiyi yazma zaman aşımından daha uzun süre bekletemez
+        if let Err(err) = response.write_to(&mut Deadline::new(&stream, WRITE_TIMEOUT)) {
+            eprintln!("Failed to write response: {}", err);
+            return;
+        }
+        if !keep_alive {
+            return;
+        }
+    }
+}
+
+// Answers a connection that could not be queued because every worker is busy
+// Tüm işçiler meşgul olduğu için kuyruğa alınamayan bağlantıya cevap verir
+// 503 tells the client to try again later; the request itself is not read
+// 503, istemciye daha sonra tekrar denemesini söyler; isteğin kendisi okunmaz
+pub fn reject_busy(stream: TcpStream) {
+    let mut response = Response::text(503, "Server is busy, try again later");
+    response.headers.push((String::from("Retry-After"), String::from("1")));
+    response.headers.push((String::from("Connection"), String::from("close")));
+    if let Err(err) = response.write_to(&mut Deadline::new(&stream, WRITE_TIMEOUT)) {
+        eprintln!("Failed to write busy response: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn request(method: &str, path: &str) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            version: String::from("HTTP/1.1"),
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn routes_by_method_and_path() {
+        let router = Router::new()
+            .route("GET", "/", |_| Response::text(200, "home"))
+            .route("POST", "/echo", |request| Response::new(200, "text/plain", request.body.clone()));
+        assert_eq!(router.handle(&request("GET", "/?x=1")).body, b"home");
+        assert_eq!(router.handle(&request("GET", "/missing")).status, 404);
+        let not_allowed = router.handle(&request("GET", "/echo"));
+        assert_eq!(not_allowed.status, 405);
+        assert!(not_allowed.headers.contains(&(String::from("Allow"), String::from("POST"))));
+    }
+
+    #[test]
+    fn answers_pipelined_requests_on_one_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let router = Router::new().route("POST", "/echo", |request| Response::new(200, "text/plain", request.body.clone()));
+            handle_client(listener.accept().unwrap().0, &router);
+        });
+
+        // Both requests are sent in one write; the second asks to close the connection
+        // İki istek tek yazmada gönderilir; ikincisi bağlantının kapatılmasını ister
+        let mut client = TcpStream::connect(address).unwrap();
+        client
+            .write_all(b"POST /echo HTTP/1.1\r\nContent-Length: 3\r\n\r\noneP")
+            .unwrap();
+        client
+            .write_all(b"OST /echo HTTP/1.1\r\nContent-Length: 3\r\nConnection: close\r\n\r\ntwo")
+            .unwrap();
+        let mut responses = String::new();
+        client.read_to_string(&mut responses).unwrap(); // Returns when the server closes / Sunucu kapatınca döner
+        server.join().unwrap();
+
+        let first = responses.find("one").unwrap();
+        let second = responses.find("two").unwrap();
+        assert!(first < second);
+        assert_eq!(responses.matches("HTTP/1.1 200 OK").count(), 2);
+        assert!(responses[..first].contains("Connection: keep-alive"));
+        assert!(responses[first..].contains("Connection: close"));
+    }
+
+    #[test]
+    fn closes_an_idle_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let router = Router::new().route("GET", "/", |_| Response::text(200, "home"));
+            handle_client(listener.accept().unwrap().0, &router);
+        });
+
+        // One request, then silence: the server answers and closes after IDLE_TIMEOUT
+        // Bir istek, sonra sessizlik: sunucu cevap verir ve IDLE_TIMEOUT sonra kapatır
+        let mut client = TcpStream::connect(address).unwrap();
+        client.write_all(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        let started = Instant::now();
+        let mut responses = String::new();
+        client.read_to_string(&mut responses).unwrap();
+        server.join().unwrap();
+
+        assert!(started.elapsed() >= IDLE_TIMEOUT);
+        assert_eq!(responses.matches("HTTP/1.1 200 OK").count(), 1);
+        assert!(responses.contains("Connection: keep-alive"));
+    }
+
+    #[test]
+    fn closes_after_the_request_limit() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let router = Router::new().route("GET", "/", |_| Response::text(200, "home"));
+            handle_client(listener.accept().unwrap().0, &router);
+        });
+
+        // Every request asks to keep the connection open, but the last one allowed is answered with close
+        // Her istek bağlantının açık kalmasını ister, ama izin verilen son istek close ile cevaplanır
+        let mut client = TcpStream::connect(address).unwrap();
+        client
+            .write_all(b"GET / HTTP/1.1\r\n\r\n".repeat(MAX_REQUESTS_PER_CONNECTION).as_slice())
+            .unwrap();
+        let mut responses = String::new();
+        client.read_to_string(&mut responses).unwrap();
+        server.join().unwrap();
+
+        let responses: Vec<&str> = responses.split("HTTP/1.1 ").skip(1).collect();
+        assert_eq!(responses.len(), MAX_REQUESTS_PER_CONNECTION);
+        assert!(responses[MAX_REQUESTS_PER_CONNECTION - 2].contains("Keep-Alive: timeout=5, max=1"));
+        let last = responses[MAX_REQUESTS_PER_CONNECTION - 1];
+        assert!(last.contains("Connection: close") && !last.contains("Keep-Alive"));
+    }
+}